@@ -0,0 +1,108 @@
+// Case conventions for `rename_all = "..."`, named the same way serde names them.
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Camel,
+    Pascal,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+    Title,
+}
+
+impl RenameRule {
+    pub(crate) const NAMES: &'static [&'static str] = &[
+        "lowercase",
+        "UPPERCASE",
+        "camelCase",
+        "PascalCase",
+        "snake_case",
+        "SCREAMING_SNAKE_CASE",
+        "kebab-case",
+        "SCREAMING-KEBAB-CASE",
+        "Title Case",
+    ];
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "camelCase" => RenameRule::Camel,
+            "PascalCase" => RenameRule::Pascal,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            "Title Case" => RenameRule::Title,
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn apply(self, ident: &str) -> String {
+        let words = split_words(ident);
+        match self {
+            // Whole-ident rules keep the underscores, like the `lowercase` option.
+            RenameRule::Lower => ident.to_lowercase(),
+            RenameRule::Upper => ident.to_uppercase(),
+            RenameRule::Camel => {
+                let mut out = String::new();
+                for (i, word) in words.iter().enumerate() {
+                    if i == 0 {
+                        out.push_str(&word.to_lowercase());
+                    } else {
+                        out.push_str(&capitalize(word));
+                    }
+                }
+                out
+            }
+            RenameRule::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            RenameRule::Snake => join(&words, "_", str::to_lowercase),
+            RenameRule::ScreamingSnake => join(&words, "_", str::to_uppercase),
+            RenameRule::Kebab => join(&words, "-", str::to_lowercase),
+            RenameRule::ScreamingKebab => join(&words, "-", str::to_uppercase),
+            RenameRule::Title => join(&words, " ", capitalize),
+        }
+    }
+}
+
+fn join(words: &[&str], sep: &str, f: impl Fn(&str) -> String) -> String {
+    words.iter().map(|word| f(word)).collect::<Vec<_>>().join(sep)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
+
+// Split an identifier into words. Underscores separate words, and so does a
+// lowercase letter or digit followed by an uppercase one (`DarkBlue`, `V2Beta`).
+// A run of capitals is an acronym that ends before the last capital when a
+// lowercase letter follows (`HTTPServer` is `HTTP` + `Server`). Digits stay
+// attached to the word they follow (`Ipv4Addr` is `Ipv4` + `Addr`).
+fn split_words(ident: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for part in ident.split('_').filter(|part| !part.is_empty()) {
+        let chars: Vec<(usize, char)> = part.char_indices().collect();
+        let mut start = 0;
+        for i in 1..chars.len() {
+            let (idx, cur) = chars[i];
+            let prev = chars[i - 1].1;
+            let next = chars.get(i + 1).map(|&(_, c)| c);
+            let boundary = cur.is_uppercase()
+                && (prev.is_lowercase()
+                    || prev.is_numeric()
+                    || (prev.is_uppercase() && next.is_some_and(char::is_lowercase)));
+            if boundary {
+                words.push(&part[start..idx]);
+                start = idx;
+            }
+        }
+        words.push(&part[start..]);
+    }
+    words
+}
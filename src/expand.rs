@@ -0,0 +1,1290 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Expr, ExprLit, ExprUnary, Fields, Generics, Ident, Lit, Meta, NestedMeta, Type, UnOp, Variant};
+
+use crate::{fields, structs};
+use crate::options::{self, Arg, Errors, Options, VariantOptions, error};
+
+// Generate the `Parse{EnumName}Error` type (unless the enum names its own
+// `error` type) and the `FromStr` impl for `input`.
+// Shared by `#[derive(FromStr)]` and the `#[derive_fromstr]` attribute, whose
+// arguments come in as `args`.
+pub(crate) fn expand(input: &DeriveInput, args: &[Arg]) -> syn::Result<TokenStream> {
+    let enum_name = &input.ident;
+    // The enum's name without any `r#`, for the names derived from it.
+    let bare_name = enum_name.unraw();
+
+    let mut errors = Errors::default();
+    // Enum-level options also come from `#[fromstr(...)]` on the enum itself.
+    let mut args = args.to_vec();
+    args.extend(options::helper_args(&input.attrs, &mut errors));
+    let opts = Options::from_args(&args, &mut errors);
+    if let Some(ty) = &opts.error
+        && (opts.error_name.is_some() || opts.error_vis.is_some() || !opts.error_derive.is_empty())
+    {
+        errors.push(error(ty, "`error_name`, `error_vis` and `error_derive` only apply to the generated error type"));
+    }
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        Data::Struct(data) => return structs::expand(input, data, &args, &opts, errors),
+        Data::Union(_) => return Err(error(enum_name, "only enums and unit or newtype structs are supported")),
+    };
+
+    // The canonical spelling of each variant (its `rename` if given, else its
+    // ident under `rename_all`) together with its aliases.
+    let mut specs = Vec::new();
+    for variant in variants {
+        let var_opts = VariantOptions::from_attrs(&variant.attrs, &opts, &mut errors);
+        let name = var_opts.rename.clone().unwrap_or_else(|| opts.variant_name(&variant.ident));
+        if var_opts.skip && (var_opts.other || !var_opts.aliases.is_empty() || var_opts.prefix.is_some()) {
+            errors.push(error(&variant.ident, "a `skip` variant is never parsed and takes no `other`, `alias` or `prefix`"));
+        }
+        let cfgs = variant.attrs.iter().filter(|attr| is_cfg(attr)).collect();
+        specs.push(VariantSpec { variant, name, opts: var_opts, cfgs });
+    }
+
+    // The `#[fromstr(other)]` variant, if any, is returned for every input no
+    // other variant matches. A unit variant keeps its own spelling as well; a
+    // variant with a field captures the input instead.
+    let mut other = None;
+    for (index, spec) in specs.iter().enumerate().filter(|(_, spec)| spec.opts.other) {
+        match &spec.variant.fields {
+            Fields::Unit => {}
+            Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+                if spec.opts.rename.is_some() || !spec.opts.aliases.is_empty() || spec.opts.prefix.is_some() {
+                    errors.push(error(
+                        &spec.variant.ident,
+                        "an `other` variant with a field is never parsed by name and takes no `rename`, `alias` or `prefix`",
+                    ));
+                }
+            }
+            _ => errors.push(error(
+                &spec.variant.ident,
+                "an `other` variant must be a unit variant or hold a single field such as `String`",
+            )),
+        }
+        if !spec.cfgs.is_empty() {
+            errors.push(error(&spec.variant.ident, "an `other` variant cannot be behind `#[cfg]`"));
+        }
+        match other {
+            Some(_) => errors.push(error(&spec.variant.ident, "only one variant can be `other`")),
+            None => other = Some(index),
+        }
+    }
+
+    // Every string `from_str` accepts, already case folded. Aliases go through
+    // the same folding as the name.
+    let mut spellings = Vec::new();
+    for (index, spec) in specs.iter().enumerate().filter(|(_, spec)| spec.is_parsed()) {
+        for spelling in std::iter::once(&spec.name).chain(&spec.opts.aliases) {
+            spellings.push(Spelling { value: opts.normalize(spelling), variant: spec.variant, index, truncated: false });
+        }
+    }
+
+    // Add extra spellings for truncated variant names if truncate is provided.
+    // Variants with data are always spelled out in full.
+    // The cut is made on the name as written, and only then case folded.
+    if let Some(trunc) = opts.truncate {
+        for (index, spec) in specs.iter().enumerate().filter(|(_, spec)| spec.is_unit() && spec.is_parsed()) {
+            match trunc.apply(&spec.name) {
+                Ok(Some(truncated)) => {
+                    let truncated = opts.normalize(truncated);
+                    spellings.push(Spelling { value: truncated, variant: spec.variant, index, truncated: true });
+                }
+                Ok(None) => {}
+                Err(message) => errors.push(error(&spec.variant.ident, message)),
+            }
+        }
+    }
+
+    // Collisions between variants behind `#[cfg]` are left for the compiler,
+    // for when both are compiled in.
+    let mut deferred = Vec::new();
+    let spellings = check_collisions(&specs, spellings, &mut errors, &mut deferred);
+    let numbers = numbers(&specs, &opts, &spellings, &mut errors, &mut deferred);
+    errors.finish()?;
+    let deferred = if deferred.is_empty() {
+        quote! {}
+    } else {
+        let cfgs = type_cfgs(input);
+        quote! {
+            #( #cfgs )*
+            const _: () = {
+                #( #deferred )*
+            };
+        }
+    };
+
+    let vis = &input.vis;
+    let error_enum_ident = error_ident(input, &opts);
+    let cfgs = type_cfgs(input);
+    let error_type = match &opts.error {
+        Some(ty) => ErrorType::Custom(ty, bare_name.to_string()),
+        None => ErrorType::Generated(&error_enum_ident),
+    };
+    let (alloc, alloc_crate) = alloc_crate(input, &opts);
+    let canonical = specs.iter().map(|spec| opts.normalize(&spec.name)).collect::<Vec<_>>();
+    let has_data = specs.iter().any(|spec| !spec.is_unit() && spec.is_parsed());
+    let captures_input = specs.iter().any(VariantSpec::captures_input);
+
+    // Generic enums get the same impls over their own parameters. Fields whose
+    // type uses a type parameter add the bounds that parsing and formatting
+    // them need; the rest are checked where the enum is declared.
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let type_params = generics.type_params().map(|param| &param.ident).collect::<Vec<_>>();
+    let mut from_str_bounds = Vec::new();
+    let mut display_bounds = Vec::new();
+    for spec in specs.iter().filter(|spec| !spec.is_marker()) {
+        for ty in spec.variant.fields.iter().map(|field| &field.ty).filter(|ty| mentions_any(ty, &type_params)) {
+            if spec.captures_input() {
+                from_str_bounds.push(quote! { #ty: for<'__s> ::core::convert::From<&'__s ::core::primitive::str> });
+            } else if spec.is_parsed() {
+                from_str_bounds.push(quote! { #ty: ::core::str::FromStr });
+                if alloc.is_some() && error_type.is_generated() {
+                    from_str_bounds.push(quote! { <#ty as ::core::str::FromStr>::Err: ::core::fmt::Display });
+                }
+            }
+            display_bounds.push(quote! { #ty: ::core::fmt::Display });
+        }
+    }
+
+    // Variants that also accept any unambiguous abbreviation of their spellings,
+    // with the minimum abbreviation length for each. A variant's own `prefix`
+    // overrides the enum-wide one. Abbreviations are checked against the
+    // spellings of every unit variant: one that takes no abbreviations has a
+    // minimum of `usize::MAX`, but still makes an abbreviation of it ambiguous.
+    let unit_spellings = spellings.iter().filter(|spelling| !spelling.truncated && specs[spelling.index].is_unit()).collect::<Vec<_>>();
+    let has_prefixes = unit_spellings.iter().any(|spelling| specs[spelling.index].opts.prefix.or(opts.prefix).is_some());
+    let prefixes = unit_spellings
+        .iter()
+        .map(|spelling| {
+            let min = match specs[spelling.index].opts.prefix.or(opts.prefix) {
+                Some(min) => quote! { #min },
+                None => quote! { ::core::primitive::usize::MAX },
+            };
+            let value = &spelling.value;
+            let index = spelling.index;
+            let cfg = specs[index].cfg();
+            quote! { #cfg (#value, #min, #index) }
+        })
+        .collect::<Vec<_>>();
+
+    // Generate match arms for each spelling of a unit variant.
+    let arms_vec = spellings
+        .iter()
+        .filter(|spelling| specs[spelling.index].is_unit())
+        .map(|spelling| {
+            let var_ident = &spelling.variant.ident;
+            let expected = &spelling.value;
+            let cfg = specs[spelling.index].cfg();
+            quote! {
+                #cfg
+                #expected => return ::core::result::Result::Ok(#enum_name::#var_ident),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Errors report the caller's `input` as given, along with the normalized
+    // string that was compared and its byte range within `input`.
+    let reports_input = other.is_none() || has_prefixes;
+    let keep_input = if reports_input || has_data {
+        quote! {
+            let __input = __s;
+        }
+    } else {
+        quote! {}
+    };
+    let span = input_span(&opts);
+
+    // Generate code to transform the input string based on flags. Fields of
+    // data-carrying variants are parsed from `raw`, which is not case folded.
+    let trim = if opts.trim {
+        quote! {
+            let __s = __s.trim();
+        }
+    } else {
+        quote! {}
+    };
+    let raw = if has_data || captures_input || (opts.trim && reports_input && error_type.is_generated()) {
+        quote! {
+            let __raw = __s;
+        }
+    } else {
+        quote! {}
+    };
+    let longest = spellings.iter().map(|spelling| spelling.value.len()).max().unwrap_or(0);
+    let lowercase = if opts.lowercase {
+        to_lowercase(&format_ident!("__s"), alloc.as_ref(), longest)
+    } else {
+        quote! {}
+    };
+
+    // Parse discriminants and declaration indices, once no spelling matched
+    // exactly. Hex needs the `0x` prefix.
+    let number_match = if numbers.is_empty() {
+        quote! {}
+    } else {
+        // Decimal is an optional `-` and digits only, like hex takes no sign:
+        // `parse` alone would also take a `+`.
+        let decimal = quote! {{
+            let __digits = __s.strip_prefix('-').unwrap_or(__s);
+            if !__digits.is_empty() && __digits.trim_start_matches(|__c: ::core::primitive::char| __c.is_ascii_digit()).is_empty() {
+                __s.parse().ok()
+            } else {
+                ::core::option::Option::None
+            }
+        }};
+        let parse = if opts.discriminant == Some(true) {
+            quote! {
+                match __s.strip_prefix("0x").or_else(|| __s.strip_prefix("0X")) {
+                    ::core::option::Option::Some(__hex) if __hex.starts_with(|__c: ::core::primitive::char| __c.is_ascii_hexdigit()) => ::core::primitive::i128::from_str_radix(__hex, 16).ok(),
+                    ::core::option::Option::Some(_) => ::core::option::Option::None,
+                    ::core::option::Option::None => #decimal,
+                }
+            }
+        } else {
+            decimal
+        };
+        let number_arms = numbers.iter().map(|&(value, index)| {
+            let value = proc_macro2::Literal::i128_suffixed(value);
+            let var_ident = &specs[index].variant.ident;
+            let cfg = specs[index].cfg();
+            quote! { #cfg #value => return ::core::result::Result::Ok(#enum_name::#var_ident), }
+        });
+        quote! {
+            let __number: ::core::option::Option<::core::primitive::i128> = #parse;
+            if let ::core::option::Option::Some(__number) = __number {
+                match __number {
+                    #( #number_arms )*
+                    _ => {}
+                }
+            }
+        }
+    };
+
+    // Parse `Name(...)` and `Name { ... }` for the variants with data, once no
+    // spelling matched exactly.
+    let data_match = if has_data {
+        let helpers = fields::helpers();
+        let data_arms = specs.iter().enumerate().filter(|(_, spec)| !spec.is_unit() && spec.is_parsed()).map(|(index, spec)| {
+            let spellings = spellings
+                .iter()
+                .filter(|spelling| spelling.index == index)
+                .map(|spelling| spelling.value.clone())
+                .collect::<Vec<_>>();
+            let arm = fields::parse_arm(enum_name, &error_type, alloc.as_ref(), &span, spec.variant, &spellings, &canonical[index]);
+            let cfg = spec.cfg();
+            quote! { #cfg #arm }
+        });
+        let fold_head = if opts.lowercase {
+            to_lowercase(&format_ident!("__head"), alloc.as_ref(), longest)
+        } else {
+            quote! {}
+        };
+        quote! {
+            #helpers
+            if let ::core::option::Option::Some((__head, __delim, __inner)) = __split_call(__raw) {
+                #fold_head
+                match __head {
+                    #( #data_arms )*
+                    _ => {}
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Each unit variant, and its canonical spelling, under its `#[cfg]`.
+    let unit_values = specs
+        .iter()
+        .filter(|spec| spec.is_unit() && !spec.opts.skip)
+        .map(|spec| {
+            let var_ident = &spec.variant.ident;
+            let cfg = spec.cfg();
+            quote! { #cfg #enum_name::#var_ident }
+        })
+        .collect::<Vec<_>>();
+    let unit_names = specs.iter().zip(&canonical).filter(|(spec, _)| spec.is_unit() && !spec.opts.skip).map(|(spec, name)| {
+        let cfg = spec.cfg();
+        quote! { #cfg #name }
+    });
+
+    // Tables of every unit variant that is not skipped and its canonical
+    // spelling, in declaration order. Variants with data have no value to list,
+    // so they are left out of all of them, as their docs say; the error's
+    // `expected` list is the one that names them. A `'static` slice of the
+    // enum itself needs its parameters to be `'static`.
+    let static_where_clause = bounded_where_clause(generics, &[quote! { Self: 'static }]);
+    let tables = quote! {
+        #( #cfgs )*
+        #[allow(dead_code)]
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            /// The canonical spelling of each of [`Self::VARIANTS`], in the same order.
+            /// Variants with data are not listed.
+            #vis const NAMES: &'static [&'static ::core::primitive::str] = &[#( #unit_names ),*];
+            /// The number of [`Self::VARIANTS`]. Variants with data are not counted.
+            #vis const COUNT: ::core::primitive::usize = Self::NAMES.len();
+
+            /// Every unit variant that is parsed from a string, in declaration order.
+            /// Variants with data are not listed.
+            #vis fn iter() -> impl ::core::iter::Iterator<Item = Self> {
+                let __variants = [#( #unit_values ),*];
+                ::core::iter::IntoIterator::into_iter(__variants)
+            }
+        }
+
+        #( #cfgs )*
+        #[allow(dead_code)]
+        impl #impl_generics #enum_name #ty_generics #static_where_clause {
+            /// Every unit variant that is parsed from a string, in declaration order.
+            /// Variants with data are not listed.
+            #vis const VARIANTS: &'static [Self] = &[#( #unit_values ),*];
+        }
+    };
+
+    // With `display`, the canonical spelling of each variant is also what it
+    // formats as, so a unit variant parses back from its `to_string`, padded
+    // to the formatter's width. Variants with data format their fields back
+    // in call syntax, unquoted: they only parse back when each field's own
+    // output does, and is neither empty nor contains a top-level comma.
+    let display = if opts.display {
+        let as_str_arms = specs.iter().zip(&canonical).map(|(spec, name)| {
+            let var_ident = &spec.variant.ident;
+            let cfg = spec.cfg();
+            quote! { #cfg #enum_name::#var_ident { .. } => #name, }
+        });
+        let display_arms = specs.iter().zip(&canonical).map(|(spec, name)| {
+            let arm = if spec.is_marker() {
+                let var_ident = &spec.variant.ident;
+                quote! {
+                    #enum_name::#var_ident { .. } => __f.pad(#name),
+                }
+            } else if spec.captures_input() {
+                // The captured input formats as itself.
+                let var_ident = &spec.variant.ident;
+                quote! {
+                    #enum_name::#var_ident(ref __input) => ::core::fmt::Display::fmt(__input, __f),
+                }
+            } else {
+                fields::display_arm(enum_name, spec.variant, name)
+            };
+            let cfg = spec.cfg();
+            quote! { #cfg #arm }
+        });
+        let display_where_clause = bounded_where_clause(generics, &display_bounds);
+        quote! {
+            #( #cfgs )*
+            impl #impl_generics #enum_name #ty_generics #where_clause {
+                /// The canonical spelling of this variant, which it formats as.
+                #vis const fn as_str(&self) -> &'static ::core::primitive::str {
+                    match *self {
+                        #( #as_str_arms )*
+                    }
+                }
+            }
+
+            #( #cfgs )*
+            impl #impl_generics ::core::fmt::Display for #enum_name #ty_generics #display_where_clause {
+                fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match *self {
+                        #( #display_arms )*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Try the abbreviations once no spelling matched exactly. If the input is
+    // long enough to abbreviate some variant, exactly one candidate variant
+    // parses; several of them are reported as ambiguous. Without `alloc`, only
+    // the first two candidates are kept.
+    let prefix_match = if !has_prefixes {
+        quote! {}
+    } else {
+        let (candidates, push, found, ambiguous) = match &alloc {
+            Some(alloc) => (
+                quote! { let mut __candidates: #alloc::vec::Vec<(::core::primitive::usize, &'static ::core::primitive::str)> = #alloc::vec::Vec::new(); },
+                quote! { __candidates.push((__index, __name)); },
+                quote! { __candidates.as_slice() },
+                quote! {
+                    Ambiguous {
+                        input: #alloc::string::ToString::to_string(__input),
+                        normalized: #alloc::string::ToString::to_string(__s),
+                        span: #span,
+                        candidates: ::core::iter::Iterator::collect(::core::iter::Iterator::map(__candidates.iter(), |&(_, __name)| __name)),
+                    }
+                },
+            ),
+            None => (
+                quote! {
+                    let mut __candidates = [(0, ""); 2];
+                    let mut __len_found = 0;
+                },
+                quote! {
+                    if __len_found < __candidates.len() {
+                        __candidates[__len_found] = (__index, __name);
+                    }
+                    __len_found += 1;
+                },
+                quote! { &__candidates[..::core::cmp::Ord::min(__len_found, 2)] },
+                quote! { Ambiguous { span: #span, candidates: [__candidates[0].1, __candidates[1].1] } },
+            ),
+        };
+        let ambiguous = error_type.build(ambiguous);
+        let index_arms = specs.iter().enumerate().filter(|(_, spec)| spec.is_unit() && spec.is_parsed()).map(|(index, spec)| {
+            let var_ident = &spec.variant.ident;
+            let cfg = spec.cfg();
+            quote! { #cfg #index => #enum_name::#var_ident, }
+        });
+        quote! {
+            const __PREFIXES: &[(&::core::primitive::str, ::core::primitive::usize, ::core::primitive::usize)] = &[#( #prefixes ),*];
+            let __len = ::core::iter::Iterator::count(__s.chars());
+            let mut __accepted = false;
+            #candidates
+            for &(__name, __min, __index) in __PREFIXES {
+                if __name.starts_with(__s) && !::core::iter::Iterator::any(&mut (#found).iter(), |&(__seen, _)| __seen == __index) {
+                    __accepted |= __len >= __min;
+                    #push
+                }
+            }
+            if __accepted {
+                match #found {
+                    [(__index, _)] => {
+                        return ::core::result::Result::Ok(match __index {
+                            #( #index_arms )*
+                            _ => ::core::unreachable!(),
+                        });
+                    }
+                    _ => return ::core::result::Result::Err(#ambiguous),
+                }
+            }
+        }
+    };
+
+    // Generate an error enum named Parse{EnumName}Error with required derives.
+    // Only kinds that can actually occur get a variant: with an `other`
+    // variant no input is unknown, only prefix matching can be ambiguous, and
+    // only variants with data have fields to get wrong.
+    let mut error_variants = Vec::new();
+    let mut error_display = Vec::new();
+    // With `trim`, blank input is reported as such rather than as unknown.
+    let empty_check = if opts.trim && other.is_none() {
+        error_variants.push(quote! {
+            /// The input was empty or all whitespace.
+            Empty,
+        });
+        error_display.push(quote! {
+            #error_enum_ident::Empty => __f.write_str("Empty input"),
+        });
+        let empty = error_type.build(quote! { Empty });
+        quote! {
+            if __s.is_empty() {
+                return ::core::result::Result::Err(#empty);
+            }
+        }
+    } else {
+        quote! {}
+    };
+    // When every accepted input is a spelling from the table, anything too long
+    // to be one (or a typo of one) is rejected before it is case folded.
+    let too_long_check = if !has_data && numbers.is_empty() && other.is_none() {
+        let max = spellings.iter().map(|spelling| spelling.value.chars().count()).max().unwrap_or(0);
+        let limit = max + opts.max_distance();
+        error_variants.push(quote! {
+            /// The input was too long to be any variant or a typo of one.
+            TooLong {
+                /// The length of the input in chars.
+                len: ::core::primitive::usize,
+                /// The length of the longest spelling in chars.
+                max: ::core::primitive::usize,
+            },
+        });
+        error_display.push(quote! {
+            #error_enum_ident::TooLong { len: __len, max: __max } => {
+                ::core::write!(__f, "Input too long: {} characters, the longest variant has {}", __len, __max)
+            }
+        });
+        let too_long = error_type.build(quote! { TooLong { len: ::core::iter::Iterator::count(__s.chars()), max: #max } });
+        quote! {
+            if ::core::iter::Iterator::nth(&mut __s.chars(), #limit).is_some() {
+                return ::core::result::Result::Err(#too_long);
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let fallback = match other.map(|index| &specs[index]) {
+        Some(spec) if spec.captures_input() => {
+            let var_ident = &spec.variant.ident;
+            quote! { ::core::result::Result::Ok(#enum_name::#var_ident(::core::convert::From::from(__raw))) }
+        }
+        Some(spec) => {
+            let var_ident = &spec.variant.ident;
+            quote! { ::core::result::Result::Ok(#enum_name::#var_ident) }
+        }
+        None => {
+            // The accepted values, in declaration order, for "expected one of".
+            let expected = specs.iter().zip(&canonical).filter(|(spec, _)| spec.is_parsed()).map(|(spec, name)| {
+                let value = match spec.variant.fields {
+                    Fields::Unit => name.clone(),
+                    Fields::Unnamed(_) => format!("{}(..)", name),
+                    Fields::Named(_) => format!("{} {{ .. }}", name),
+                };
+                let cfg = spec.cfg();
+                quote! { #cfg #value }
+            });
+            let max_expected = opts.max_expected.unwrap_or(usize::MAX);
+            let write_expected = quote! {
+                if !__expected.is_empty() {
+                    let __shown = ::core::cmp::Ord::min(__expected.len(), #max_expected);
+                    __f.write_str(" (expected one of: ")?;
+                    for (__i, __name) in ::core::iter::Iterator::enumerate(__expected[..__shown].iter()) {
+                        if __i > 0 {
+                            __f.write_str(", ")?;
+                        }
+                        __f.write_str(__name)?;
+                    }
+                    if __shown < __expected.len() {
+                        ::core::write!(__f, " and {} more", __expected.len() - __shown)?;
+                    }
+                    __f.write_str(")")?;
+                }
+                ::core::result::Result::Ok(())
+            };
+            match &alloc {
+                Some(alloc) => {
+                    error_variants.push(quote! {
+                        /// The input matched no variant.
+                        UnknownVariant {
+                            /// The input as given.
+                            input: #alloc::string::String,
+                            /// The input as compared, after `trim` and `lowercase`.
+                            normalized: #alloc::string::String,
+                            /// The byte range of the input that was compared.
+                            span: ::core::ops::Range<::core::primitive::usize>,
+                            /// The accepted values, in declaration order.
+                            expected: &'static [&'static ::core::primitive::str],
+                            /// The closest spellings to the input, closest first.
+                            suggestions: #alloc::vec::Vec<&'static ::core::primitive::str>,
+                        },
+                    });
+                    error_display.push(quote! {
+                        #error_enum_ident::UnknownVariant { input: ref __input, expected: __expected, suggestions: ref __suggestions, .. } => {
+                            ::core::write!(__f, "Unknown variant {:?}", __input)?;
+                            for (__i, __suggestion) in ::core::iter::Iterator::enumerate(__suggestions.iter()) {
+                                let __sep = match __i {
+                                    0 => ", did you mean ",
+                                    _ if __i + 1 == __suggestions.len() => " or ",
+                                    _ => ", ",
+                                };
+                                ::core::write!(__f, "{}{:?}", __sep, __suggestion)?;
+                            }
+                            if !__suggestions.is_empty() {
+                                __f.write_str("?")?;
+                            }
+                            #write_expected
+                        }
+                    });
+                }
+                None => {
+                    error_variants.push(quote! {
+                        /// The input matched no variant.
+                        UnknownVariant {
+                            /// The byte range of the input that was compared.
+                            span: ::core::ops::Range<::core::primitive::usize>,
+                            /// The accepted values, in declaration order.
+                            expected: &'static [&'static ::core::primitive::str],
+                        },
+                    });
+                    error_display.push(quote! {
+                        #error_enum_ident::UnknownVariant { span: ref __span, expected: __expected } => {
+                            ::core::write!(__f, "Unknown variant at {}..{}", __span.start, __span.end)?;
+                            #write_expected
+                        }
+                    });
+                }
+            }
+            match (&error_type, &alloc) {
+                (ErrorType::Generated(_), Some(alloc)) => {
+                    let suggest = suggestions(&specs, &spellings, opts.max_distance(), alloc);
+                    quote! {
+                        let __suggestions = #suggest;
+                        ::core::result::Result::Err(#error_enum_ident::UnknownVariant {
+                            input: #alloc::string::ToString::to_string(__input),
+                            normalized: #alloc::string::ToString::to_string(__s),
+                            span: #span,
+                            expected: &[#( #expected ),*],
+                            suggestions: __suggestions,
+                        })
+                    }
+                }
+                (ErrorType::Generated(_), None) => quote! {
+                    ::core::result::Result::Err(#error_enum_ident::UnknownVariant { span: #span, expected: &[#( #expected ),*] })
+                },
+                (ErrorType::Custom(..), _) => {
+                    let unknown = error_type.build(quote! { UnknownVariant });
+                    quote! { ::core::result::Result::Err(#unknown) }
+                }
+            }
+        }
+    };
+    // Without `alloc`, errors keep the span of the input rather than a copy of
+    // it, only the first two ambiguous candidates, and no field error text.
+    match &alloc {
+        Some(alloc) if has_prefixes => {
+            error_variants.push(quote! {
+                /// The input abbreviates more than one variant.
+                Ambiguous {
+                    /// The input as given.
+                    input: #alloc::string::String,
+                    /// The input as compared, after `trim` and `lowercase`.
+                    normalized: #alloc::string::String,
+                    /// The byte range of the input that was compared.
+                    span: ::core::ops::Range<::core::primitive::usize>,
+                    /// The spellings the input abbreviates.
+                    candidates: #alloc::vec::Vec<&'static ::core::primitive::str>,
+                },
+            });
+            error_display.push(quote! {
+                #error_enum_ident::Ambiguous { input: ref __input, candidates: ref __candidates, .. } => {
+                    ::core::write!(__f, "Ambiguous variant {:?} (could be ", __input)?;
+                    for (__i, __candidate) in ::core::iter::Iterator::enumerate(__candidates.iter()) {
+                        if __i > 0 {
+                            __f.write_str(", ")?;
+                        }
+                        __f.write_str(__candidate)?;
+                    }
+                    __f.write_str(")")
+                }
+            });
+        }
+        None if has_prefixes => {
+            error_variants.push(quote! {
+                /// The input abbreviates more than one variant.
+                Ambiguous {
+                    /// The byte range of the input that was compared.
+                    span: ::core::ops::Range<::core::primitive::usize>,
+                    /// The first two spellings the input abbreviates.
+                    candidates: [&'static ::core::primitive::str; 2],
+                },
+            });
+            error_display.push(quote! {
+                #error_enum_ident::Ambiguous { span: ref __span, candidates: __candidates } => {
+                    ::core::write!(__f, "Ambiguous variant at {}..{} (could be {} or {})", __span.start, __span.end, __candidates[0], __candidates[1])
+                }
+            });
+        }
+        _ => {}
+    }
+    match &alloc {
+        Some(alloc) if has_data => {
+            error_variants.push(quote! {
+                /// A field of a variant with data failed to parse.
+                InvalidField {
+                    /// The canonical spelling of the variant.
+                    variant: &'static ::core::primitive::str,
+                    /// The name or index of the field.
+                    field: &'static ::core::primitive::str,
+                    /// The field's own error message.
+                    error: #alloc::string::String,
+                },
+                /// The input named a variant with data but did not match its shape.
+                MalformedVariant {
+                    /// The canonical spelling of the variant.
+                    variant: &'static ::core::primitive::str,
+                    /// The input as given.
+                    input: #alloc::string::String,
+                },
+            });
+            error_display.push(quote! {
+                #error_enum_ident::InvalidField { variant: __variant, field: __field, error: ref __error } => {
+                    ::core::write!(__f, "Invalid field {} of {}: {}", __field, __variant, __error)
+                }
+                #error_enum_ident::MalformedVariant { variant: __variant, input: ref __input } => {
+                    ::core::write!(__f, "Malformed {}: {}", __variant, __input)
+                }
+            });
+        }
+        None if has_data => {
+            error_variants.push(quote! {
+                /// A field of a variant with data failed to parse.
+                InvalidField {
+                    /// The canonical spelling of the variant.
+                    variant: &'static ::core::primitive::str,
+                    /// The name or index of the field.
+                    field: &'static ::core::primitive::str,
+                },
+                /// The input named a variant with data but did not match its shape.
+                MalformedVariant {
+                    /// The canonical spelling of the variant.
+                    variant: &'static ::core::primitive::str,
+                    /// The byte range of the input that was compared.
+                    span: ::core::ops::Range<::core::primitive::usize>,
+                },
+            });
+            error_display.push(quote! {
+                #error_enum_ident::InvalidField { variant: __variant, field: __field } => {
+                    ::core::write!(__f, "Invalid field {} of {}", __field, __variant)
+                }
+                #error_enum_ident::MalformedVariant { variant: __variant, span: ref __span } => {
+                    ::core::write!(__f, "Malformed {} at {}..{}", __variant, __span.start, __span.end)
+                }
+            });
+        }
+        _ => {}
+    }
+    let error_enum = error_enum(input, &opts, &error_enum_ident, &error_variants, &error_display, None);
+    let err_type = error_type.ty();
+    let constructor = error_type.constructor();
+
+    // Generate the error enum and the FromStr implementation using it.
+    let from_str_where_clause = bounded_where_clause(generics, &from_str_bounds);
+    Ok(quote! {
+        #deferred
+        #alloc_crate
+        #tables
+        #display
+        #error_enum
+        #( #cfgs )*
+        impl #impl_generics ::core::str::FromStr for #enum_name #ty_generics #from_str_where_clause {
+            type Err = #err_type;
+            fn from_str(__s: &::core::primitive::str) -> ::core::result::Result<Self, <Self as ::core::str::FromStr>::Err> {
+                #constructor
+                #keep_input
+                #trim
+                #too_long_check
+                #raw
+                #lowercase
+                match __s {
+                    #( #arms_vec )*
+                    _ => {}
+                }
+                #number_match
+                #data_match
+                #prefix_match
+                #empty_check
+                #fallback
+            }
+        }
+    })
+}
+
+// The byte range within `__input` of what `from_str` compared: all of it, or
+// with `trim`, `__raw` (the input trimmed but not case folded).
+pub(crate) fn input_span(opts: &Options) -> TokenStream {
+    if opts.trim {
+        quote! {{
+            let __start = __input.len() - __input.trim_start().len();
+            __start..__start + __raw.len()
+        }}
+    } else {
+        quote! { 0..__input.len() }
+    }
+}
+
+// The name of the generated error type: `error_name`, or `Parse{Name}Error`.
+pub(crate) fn error_ident(input: &DeriveInput, opts: &Options) -> Ident {
+    opts.error_name.clone().unwrap_or_else(|| Ident::new(&format!("Parse{}Error", input.ident.unraw()), input.ident.span()))
+}
+
+// `#[cfg]` on the type applies to everything generated for it.
+pub(crate) fn type_cfgs(input: &DeriveInput) -> Vec<&Attribute> {
+    input.attrs.iter().filter(|attr| is_cfg(attr)).collect()
+}
+
+// Unless `no_alloc`, errors hold strings from the `alloc` crate, which is
+// linked under a name of its own so that it also works in crates without
+// `extern crate alloc`. Returns that name and the `extern crate` item.
+pub(crate) fn alloc_crate(input: &DeriveInput, opts: &Options) -> (Option<Ident>, TokenStream) {
+    if opts.no_alloc {
+        return (None, quote! {});
+    }
+    let alloc = format_ident!("__derive_fromstr_alloc_{}", input.ident.unraw());
+    let cfgs = type_cfgs(input);
+    let item = quote! {
+        #( #cfgs )*
+        extern crate alloc as #alloc;
+    };
+    (Some(alloc), item)
+}
+
+// The generated error enum named `ident`, with the error kinds in `variants`
+// and their `Display` arms in `display`, unless `opts` names its own `error`
+// type. Besides the type's `#[cfg]`s, it takes its `#[doc(hidden)]` and the
+// like, but not its doc comment. An enum's error holds no values of the type,
+// so it takes none of its parameters.
+//
+// For a newtype struct, `field` is the type whose error the single `Invalid`
+// kind wraps. The field uses all of the struct's parameters, so the error
+// takes them too, bounded by the field's `FromStr`. The field's error need not
+// be `Debug`, `Display`, comparable or an `Error`, so the error is each of
+// those, with the field's error as its `source`, only when that one is. The
+// bounds hold for every lifetime so that an unmet one leaves the impl out
+// instead of failing to compile.
+pub(crate) fn error_enum(input: &DeriveInput, opts: &Options, ident: &Ident, variants: &[TokenStream], display: &[TokenStream], field: Option<&Type>) -> TokenStream {
+    if opts.error.is_some() {
+        return quote! {};
+    }
+    let error_vis = opts.error_vis.as_ref().unwrap_or(&input.vis);
+    let cfgs = type_cfgs(input);
+    let doc_attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc") && !matches!(attr.parse_meta(), Ok(Meta::NameValue(_))));
+    let error_doc = format!(" The error returned when parsing a [`{}`] fails.", input.ident.unraw());
+    let derives = [("Debug", quote! { ::core::fmt::Debug }), ("PartialEq", quote! { ::core::cmp::PartialEq }), ("Eq", quote! { ::core::cmp::Eq })];
+    let extra_derives = opts
+        .error_derive
+        .iter()
+        .filter(|path| !derives.iter().any(|(name, _)| path.segments.last().is_some_and(|segment| segment.ident == name)));
+
+    let Some(ty) = field else {
+        let derives = derives.iter().map(|(_, path)| path);
+        return quote! {
+            #[doc = #error_doc]
+            #( #doc_attrs )*
+            #( #cfgs )*
+            #[derive(#( #derives, )* #( #extra_derives ),*)]
+            #error_vis enum #ident {
+                #( #variants )*
+            }
+
+            #( #cfgs )*
+            impl ::core::error::Error for #ident {}
+
+            #( #cfgs )*
+            impl ::core::fmt::Display for #ident {
+                fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match *self {
+                        #( #display )*
+                    }
+                }
+            }
+        };
+    };
+
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let field_bound = if generics.params.is_empty() { vec![] } else { vec![quote! { #ty: ::core::str::FromStr }] };
+    let where_clause = bounded_where_clause(generics, &field_bound);
+    let field_error = quote! { <#ty as ::core::str::FromStr>::Err };
+    let bounded = |bound: TokenStream| {
+        let mut bounds = field_bound.clone();
+        bounds.push(quote! { for<'__a> #field_error: #bound });
+        bounded_where_clause(generics, &bounds)
+    };
+    let debug_where_clause = bounded(quote! { ::core::fmt::Debug });
+    let display_where_clause = bounded(quote! { ::core::fmt::Display });
+    let eq_where_clause = bounded(quote! { ::core::cmp::PartialEq });
+    let full_eq_where_clause = bounded(quote! { ::core::cmp::Eq });
+    let error_where_clause = bounded(quote! { ::core::error::Error + 'static });
+    let extra_derives = extra_derives.collect::<Vec<_>>();
+    let derive = if extra_derives.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[derive(#( #extra_derives ),*)] }
+    };
+    quote! {
+        #[doc = #error_doc]
+        #( #doc_attrs )*
+        #( #cfgs )*
+        #derive
+        #error_vis enum #ident #generics #where_clause {
+            #( #variants )*
+        }
+
+        #( #cfgs )*
+        impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #debug_where_clause {
+            fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let #ident::Invalid(__error) = self;
+                __f.debug_tuple("Invalid").field(__error).finish()
+            }
+        }
+
+        #( #cfgs )*
+        impl #impl_generics ::core::fmt::Display for #ident #ty_generics #display_where_clause {
+            fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match *self {
+                    #( #display )*
+                }
+            }
+        }
+
+        #( #cfgs )*
+        impl #impl_generics ::core::cmp::PartialEq for #ident #ty_generics #eq_where_clause {
+            fn eq(&self, __other: &Self) -> ::core::primitive::bool {
+                let (#ident::Invalid(__error), #ident::Invalid(__other)) = (self, __other);
+                __error == __other
+            }
+        }
+
+        #( #cfgs )*
+        impl #impl_generics ::core::cmp::Eq for #ident #ty_generics #full_eq_where_clause {}
+
+        #( #cfgs )*
+        impl #impl_generics ::core::error::Error for #ident #ty_generics #error_where_clause {
+            fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+                let #ident::Invalid(__error) = self;
+                ::core::option::Option::Some(__error)
+            }
+        }
+    }
+}
+
+// How `from_str` builds its errors: as a kind of the generated error enum, or
+// through `Type::parse_error(enum_name, input)` for a user-supplied `error`
+// type, which gets no more detail than that.
+pub(crate) enum ErrorType<'a> {
+    Generated(&'a Ident),
+    Custom(&'a Type, String),
+}
+
+impl ErrorType<'_> {
+    pub(crate) fn is_generated(&self) -> bool {
+        matches!(self, ErrorType::Generated(_))
+    }
+
+    // The `Err` type of the `FromStr` impl.
+    pub(crate) fn ty(&self) -> TokenStream {
+        match self {
+            ErrorType::Generated(ident) => quote! { #ident },
+            ErrorType::Custom(ty, _) => quote! { #ty },
+        }
+    }
+
+    // An error of `kind`, e.g. `Empty` or `TooLong { len, max }`.
+    pub(crate) fn build(&self, kind: TokenStream) -> TokenStream {
+        match self {
+            ErrorType::Generated(ident) => quote! { #ident::#kind },
+            ErrorType::Custom(_, name) => quote! { __parse_error(#name, __input) },
+        }
+    }
+
+    // The start of `from_str` for a user-supplied `error` type, which binds
+    // `Type::parse_error` to the signature it must have. A mismatch is then
+    // reported at the type, naming that signature.
+    pub(crate) fn constructor(&self) -> TokenStream {
+        let ErrorType::Custom(ty, _) = self else {
+            return quote! {};
+        };
+        let signature = quote_spanned! {ty.span()=>
+            fn(&'static ::core::primitive::str, &::core::primitive::str) -> #ty
+        };
+        let parse_error = quote_spanned! {ty.span()=> <#ty>::parse_error };
+        quote! {
+            let __parse_error: #signature = #parse_error;
+        }
+    }
+}
+
+// An expression for the (at most three) spellings within `max_distance` edits
+// of `s`, closest first, for "did you mean" suggestions.
+fn suggestions(specs: &[VariantSpec<'_>], spellings: &[Spelling<'_>], max_distance: usize, alloc: &Ident) -> TokenStream {
+    let candidates = spellings.iter().filter(|spelling| !spelling.truncated).map(|spelling| {
+        let value = &spelling.value;
+        let cfg = specs[spelling.index].cfg();
+        quote! { #cfg #value }
+    });
+    if max_distance == 0 {
+        return quote! { #alloc::vec::Vec::new() };
+    }
+    quote! {{
+        // Levenshtein distance over chars.
+        fn __distance(__a: &::core::primitive::str, __b: &::core::primitive::str) -> ::core::primitive::usize {
+            let __b: #alloc::vec::Vec<::core::primitive::char> = ::core::iter::Iterator::collect(__b.chars());
+            let mut __row: #alloc::vec::Vec<::core::primitive::usize> = ::core::iter::Iterator::collect(0..=__b.len());
+            for (__i, __ca) in ::core::iter::Iterator::enumerate(__a.chars()) {
+                let mut __diagonal = __row[0];
+                __row[0] = __i + 1;
+                for (__j, &__cb) in ::core::iter::Iterator::enumerate(__b.iter()) {
+                    let __above = __row[__j + 1];
+                    __row[__j + 1] = if __ca == __cb { __diagonal } else { 1 + ::core::cmp::Ord::min(::core::cmp::Ord::min(__diagonal, __row[__j]), __above) };
+                    __diagonal = __above;
+                }
+            }
+            __row[__b.len()]
+        }
+
+        const __CANDIDATES: &[&::core::primitive::str] = &[#( #candidates ),*];
+        let __len = ::core::iter::Iterator::count(__s.chars());
+        // Replacing every char of either side is no resemblance at all.
+        let mut __ranked: #alloc::vec::Vec<(::core::primitive::usize, &'static ::core::primitive::str)> = #alloc::vec::Vec::new();
+        for &__candidate in __CANDIDATES {
+            let __candidate_len = ::core::iter::Iterator::count(__candidate.chars());
+            if __candidate_len.abs_diff(__len) > #max_distance {
+                continue;
+            }
+            let __edits = __distance(__s, __candidate);
+            if __edits <= #max_distance && __edits < ::core::cmp::Ord::min(__len, __candidate_len) {
+                __ranked.push((__edits, __candidate));
+            }
+        }
+        __ranked.sort_by_key(|&(__edits, _)| __edits);
+        __ranked.truncate(3);
+        ::core::iter::Iterator::collect(::core::iter::Iterator::map(::core::iter::IntoIterator::into_iter(__ranked), |(_, __candidate)| __candidate))
+    }}
+}
+
+// Shadow `var` with its lowercase form. Without `alloc`, it is folded into a
+// buffer that fits the `longest` spelling; input that does not fit can match
+// no spelling and is left as it is.
+pub(crate) fn to_lowercase(var: &Ident, alloc: Option<&Ident>, longest: usize) -> TokenStream {
+    if alloc.is_some() {
+        return quote! {
+            let __temp = #var.to_lowercase();
+            let #var = __temp.as_str();
+        };
+    }
+    quote! {
+        let mut __folded = [0u8; #longest];
+        let mut __len = 0;
+        for __c in ::core::iter::Iterator::flat_map(#var.chars(), ::core::primitive::char::to_lowercase) {
+            if __len + __c.len_utf8() > __folded.len() {
+                __len = ::core::primitive::usize::MAX;
+                break;
+            }
+            __len += __c.encode_utf8(&mut __folded[__len..]).len();
+        }
+        let #var = match __folded.get(..__len) {
+            ::core::option::Option::Some(__bytes) => ::core::str::from_utf8(__bytes).unwrap_or(#var),
+            ::core::option::Option::None => #var,
+        };
+    }
+}
+
+// A variant together with its options and canonical spelling.
+struct VariantSpec<'a> {
+    variant: &'a Variant,
+    name: String,
+    opts: VariantOptions,
+    // The variant's `#[cfg]`s, repeated on everything generated for it.
+    cfgs: Vec<&'a Attribute>,
+}
+
+impl VariantSpec<'_> {
+    fn cfg(&self) -> TokenStream {
+        let cfgs = &self.cfgs;
+        quote! { #( #cfgs )* }
+    }
+
+    fn is_unit(&self) -> bool {
+        matches!(self.variant.fields, Fields::Unit)
+    }
+
+    // An `other` variant with a field holds the unmatched input.
+    fn captures_input(&self) -> bool {
+        self.opts.other && !self.is_unit()
+    }
+
+    // A variant whose fields are all `PhantomData`, there only to use a type
+    // or lifetime parameter. It is never parsed and formats as its name.
+    fn is_marker(&self) -> bool {
+        !self.variant.fields.is_empty() && self.variant.fields.iter().all(|field| is_phantom_data(&field.ty))
+    }
+
+    // Whether the variant is parsed from its own spellings.
+    fn is_parsed(&self) -> bool {
+        !self.opts.skip && !self.captures_input() && !self.is_marker()
+    }
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|segment| segment.ident == "PhantomData"),
+        Type::Group(group) => is_phantom_data(&group.elem),
+        _ => false,
+    }
+}
+
+// Whether `ty` names one of the enum's type `params`.
+pub(crate) fn mentions_any(ty: &Type, params: &[&Ident]) -> bool {
+    fn walk(tokens: TokenStream, params: &[&Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => params.contains(&&ident),
+            TokenTree::Group(group) => walk(group.stream(), params),
+            _ => false,
+        })
+    }
+    walk(ty.to_token_stream(), params)
+}
+
+// The enum's own `where` clause with `bounds` added, if there is anything in it.
+pub(crate) fn bounded_where_clause(generics: &Generics, bounds: &[TokenStream]) -> TokenStream {
+    let predicates = generics.where_clause.iter().flat_map(|clause| &clause.predicates);
+    if generics.where_clause.as_ref().is_none_or(|clause| clause.predicates.is_empty()) && bounds.is_empty() {
+        return quote! {};
+    }
+    quote! { where #( #predicates, )* #( #bounds, )* }
+}
+
+// `#[cfg(...)]`, or a `#[cfg_attr(...)]` that only adds `cfg`s. Other
+// `cfg_attr`s are not copied, since their attributes would not apply to a
+// match arm.
+fn is_cfg(attr: &Attribute) -> bool {
+    if attr.path.is_ident("cfg") {
+        return true;
+    }
+    match attr.parse_meta() {
+        Ok(Meta::List(list)) if list.path.is_ident("cfg_attr") && list.nested.len() > 1 => list
+            .nested
+            .iter()
+            .skip(1)
+            .all(|nested| matches!(nested, NestedMeta::Meta(Meta::List(inner)) if inner.path.is_ident("cfg"))),
+        _ => false,
+    }
+}
+
+// A string `from_str` accepts, and the variant (and its position) it parses to.
+struct Spelling<'a> {
+    value: String,
+    variant: &'a Variant,
+    index: usize,
+    // Produced by `truncate(N)` rather than spelled out.
+    truncated: bool,
+}
+
+// The numbers each unit variant parses from: its discriminant with
+// `discriminant` and its declaration index with `index`. Numbers shared by two
+// variants, or equal to another variant's spelling, are reported.
+fn numbers(specs: &[VariantSpec<'_>], opts: &Options, spellings: &[Spelling<'_>], errors: &mut Errors, deferred: &mut Vec<TokenStream>) -> Vec<(i128, usize)> {
+    let mut numbers: Vec<(i128, usize)> = Vec::new();
+    let mut add = |value: i128, index: usize, errors: &mut Errors, deferred: &mut Vec<TokenStream>| match numbers.iter().find(|&&(seen, _)| seen == value) {
+        Some(&(_, seen)) if seen == index => {}
+        Some(&(_, seen)) => {
+            if collision(errors, deferred, &specs[seen], &specs[index], &value.to_string()) {
+                numbers.push((value, index));
+            }
+        }
+        None => numbers.push((value, index)),
+    };
+
+    // Implicit discriminants count up from the previous one. Indices, and
+    // implicit discriminants, after a variant behind `#[cfg]` depend on whether
+    // it is compiled in, so they cannot be parsed.
+    let mut next = 0i128;
+    let mut gated_index: Option<&Variant> = None;
+    let mut gated_discriminant: Option<&Variant> = None;
+    let mut reported = false;
+    for (index, spec) in specs.iter().enumerate() {
+        if spec.variant.discriminant.is_some() {
+            gated_discriminant = None;
+        }
+        let gated = gated_index.filter(|_| opts.index).or(gated_discriminant.filter(|_| opts.discriminant.is_some()));
+        if let Some(gated) = gated
+            && spec.is_unit()
+            && !spec.opts.skip
+            && !reported
+        {
+            errors.push(error(
+                &gated.ident,
+                format_args!("the number `{}` parses from depends on whether `{}` is compiled in", spec.variant.ident, gated.ident),
+            ));
+            reported = true;
+        }
+        if !spec.cfgs.is_empty() {
+            gated_index.get_or_insert(spec.variant);
+            gated_discriminant.get_or_insert(spec.variant);
+        }
+        let discriminant = match &spec.variant.discriminant {
+            Some((_, expr)) => match int_literal(expr) {
+                Some(value) => value,
+                None => {
+                    if opts.discriminant.is_some() {
+                        errors.push(error(expr, "only integer literal discriminants can be parsed"));
+                    }
+                    next
+                }
+            },
+            None => next,
+        };
+        next = discriminant.wrapping_add(1);
+        if !spec.is_unit() || spec.opts.skip {
+            continue;
+        }
+        if opts.discriminant.is_some() {
+            add(discriminant, index, errors, deferred);
+        }
+        if opts.index {
+            add(index as i128, index, errors, deferred);
+        }
+    }
+
+    // A spelling that reads as a number shadows that number.
+    for spelling in spellings {
+        let number = match spelling.value.strip_prefix("0x").or_else(|| spelling.value.strip_prefix("0X")) {
+            Some(hex) if opts.discriminant == Some(true) => i128::from_str_radix(hex, 16).ok(),
+            _ => spelling.value.parse::<i128>().ok(),
+        };
+        if let Some(&(_, seen)) = number.and_then(|number| numbers.iter().find(|&&(value, _)| value == number))
+            && seen != spelling.index
+        {
+            collision(errors, deferred, &specs[seen], &specs[spelling.index], &spelling.value);
+        }
+    }
+    numbers
+}
+
+// The value of `3` or `-3`.
+fn int_literal(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(lit_int), .. }) => lit_int.base10_parse().ok(),
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr, .. }) => int_literal(expr).map(|value| -value),
+        Expr::Group(group) => int_literal(&group.expr),
+        Expr::Paren(paren) => int_literal(&paren.expr),
+        _ => None,
+    }
+}
+
+// Drop spellings a variant produces more than once, and report every spelling
+// produced by two different variants: only the first of them could ever match.
+// A spelling whose collision is deferred is kept, since only one of the two
+// variants may be compiled in.
+fn check_collisions<'a>(specs: &[VariantSpec<'_>], spellings: Vec<Spelling<'a>>, errors: &mut Errors, deferred: &mut Vec<TokenStream>) -> Vec<Spelling<'a>> {
+    let mut unique: Vec<Spelling<'_>> = Vec::new();
+    for spelling in spellings {
+        match unique.iter().find(|seen| seen.value == spelling.value) {
+            Some(seen) if seen.index == spelling.index => {}
+            Some(seen) => {
+                if collision(errors, deferred, &specs[seen.index], &specs[spelling.index], &spelling.value) {
+                    unique.push(spelling);
+                }
+            }
+            None => unique.push(spelling),
+        }
+    }
+    unique
+}
+
+// Report `value` as parsing to both `first` and `second`. If either is behind
+// `#[cfg]`, the report is a `compile_error!` under the `#[cfg]`s of both,
+// added to `deferred`, and the return value is true.
+fn collision(errors: &mut Errors, deferred: &mut Vec<TokenStream>, first: &VariantSpec<'_>, second: &VariantSpec<'_>, value: &str) -> bool {
+    let (first_cfg, second_cfg) = (first.cfg(), second.cfg());
+    let (first, second) = (first.variant, second.variant);
+    let raw = [first, second].iter().any(|variant| variant.ident.to_string().starts_with("r#"));
+    let message = format!(
+        "`{}` and `{}` both parse from {:?}{}",
+        first.ident,
+        second.ident,
+        value,
+        if raw { " (raw identifiers are spelled without `r#`)" } else { "" },
+    );
+    if !first_cfg.is_empty() || !second_cfg.is_empty() {
+        let message = format!("derive_fromstr: {} when both are compiled in", message);
+        deferred.push(quote_spanned! {second.ident.span()=>
+            #first_cfg
+            #second_cfg
+            ::core::compile_error!(#message);
+        });
+        return true;
+    }
+    errors.push(error(&second.ident, message));
+    errors.push(error(&first.ident, format_args!("{:?} is first produced here", value)));
+    false
+}
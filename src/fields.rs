@@ -0,0 +1,211 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Fields, Ident, Variant};
+
+use crate::expand::ErrorType;
+
+// Parsing and formatting of data-carrying variants, written the way they are
+// constructed: `Rgb(10, 20, 30)` and `Rect { w: 3, h: 4 }`. Every field goes
+// through its own `FromStr`, and its error through `Display`.
+
+// Helper functions emitted once into `from_str` when the enum has a variant
+// with fields.
+pub(crate) fn helpers() -> TokenStream {
+    quote! {
+        // Split `Name(...)` or `Name { ... }` into the name, the opening
+        // delimiter and the contents, if the closing delimiter ends the input.
+        fn __split_call(__s: &::core::primitive::str) -> ::core::option::Option<(&::core::primitive::str, ::core::primitive::char, ::core::option::Option<&::core::primitive::str>)> {
+            let __open = __s.find(['(', '{'])?;
+            let (__delim, __close) = if __s[__open..].starts_with('(') { ('(', ')') } else { ('{', '}') };
+            ::core::option::Option::Some((__s[..__open].trim_end(), __delim, __s[__open + 1..].strip_suffix(__close)))
+        }
+
+        // Split the contents of a call at the commas that are not nested in
+        // brackets. A trailing comma is allowed.
+        fn __split_fields(__s: &::core::primitive::str) -> impl ::core::iter::Iterator<Item = &::core::primitive::str> {
+            let mut __rest = ::core::option::Option::Some(__s);
+            ::core::iter::from_fn(move || {
+                let __s = __rest?;
+                let mut __depth = 0usize;
+                for (__i, __c) in __s.char_indices() {
+                    match __c {
+                        '(' | '[' | '{' => __depth += 1,
+                        ')' | ']' | '}' => __depth = __depth.saturating_sub(1),
+                        ',' if __depth == 0 => {
+                            __rest = ::core::option::Option::Some(&__s[__i + 1..]);
+                            return ::core::option::Option::Some(__s[..__i].trim());
+                        }
+                        _ => {}
+                    }
+                }
+                __rest = ::core::option::Option::None;
+                ::core::option::Option::Some(__s.trim()).filter(|__last| !__last.is_empty())
+            })
+        }
+    }
+}
+
+// A match arm on the (case folded) name before the delimiter that parses
+// `variant` from the contents. `spellings` are the names the variant accepts
+// and `canonical` is the one reported in errors. Errors keep the input and the
+// field's error as strings from the `alloc` crate, if given, and otherwise
+// just the `span` of the input.
+pub(crate) fn parse_arm(
+    enum_name: &Ident,
+    error_type: &ErrorType<'_>,
+    alloc: Option<&Ident>,
+    span: &TokenStream,
+    variant: &Variant,
+    spellings: &[String],
+    canonical: &str,
+) -> TokenStream {
+    let var_ident = &variant.ident;
+    let malformed = error_type.build(match alloc {
+        Some(alloc) => quote! {
+            MalformedVariant { variant: #canonical, input: #alloc::string::ToString::to_string(__input) }
+        },
+        None => quote! { MalformedVariant { variant: #canonical, span: #span } },
+    });
+    let err = match alloc {
+        Some(_) if error_type.is_generated() => quote! { __err },
+        _ => quote! { _ },
+    };
+    let parse_field = |ty: &syn::Type, field: &str, value: TokenStream| {
+        let invalid = error_type.build(match alloc {
+            Some(alloc) => quote! {
+                InvalidField { variant: #canonical, field: #field, error: #alloc::string::ToString::to_string(&__err) }
+            },
+            None => quote! { InvalidField { variant: #canonical, field: #field } },
+        });
+        quote! {
+            match <#ty as ::core::str::FromStr>::from_str(#value) {
+                ::core::result::Result::Ok(__value) => __value,
+                ::core::result::Result::Err(#err) => return ::core::result::Result::Err(#invalid),
+            }
+        }
+    };
+
+    let (delim, body) = match &variant.fields {
+        Fields::Named(named) if named.named.is_empty() => {
+            let body = quote! {
+                if ::core::iter::Iterator::next(&mut __split_fields(__inner)).is_some() {
+                    return ::core::result::Result::Err(#malformed);
+                }
+                return ::core::result::Result::Ok(#enum_name::#var_ident {});
+            };
+            ('{', body)
+        }
+        Fields::Named(named) => {
+            let count = named.named.len();
+            let names = named.named.iter().map(|field| field.ident.as_ref().unwrap().unraw().to_string()).collect::<Vec<_>>();
+            let indices = 0..count;
+            let values = named.named.iter().zip(&names).enumerate().map(|(index, (field, name))| {
+                let field_ident = &field.ident;
+                let value = parse_field(&field.ty, name, quote! { __value });
+                quote! {
+                    #field_ident: match __slots[#index] {
+                        ::core::option::Option::Some(__value) => #value,
+                        ::core::option::Option::None => return ::core::result::Result::Err(#malformed),
+                    }
+                }
+            });
+            let body = quote! {
+                let mut __slots: [::core::option::Option<&::core::primitive::str>; #count] = [::core::option::Option::None; #count];
+                for __field in __split_fields(__inner) {
+                    let ::core::option::Option::Some((__name, __value)) = __field.split_once(':') else {
+                        return ::core::result::Result::Err(#malformed);
+                    };
+                    let __index = match __name.trim() {
+                        #( #names => #indices, )*
+                        _ => return ::core::result::Result::Err(#malformed),
+                    };
+                    if __slots[__index].replace(__value.trim()).is_some() {
+                        return ::core::result::Result::Err(#malformed);
+                    }
+                }
+                return ::core::result::Result::Ok(#enum_name::#var_ident { #( #values ),* });
+            };
+            ('{', body)
+        }
+        Fields::Unnamed(unnamed) => {
+            let count = unnamed.unnamed.len();
+            let values = unnamed.unnamed.iter().enumerate().map(|(index, field)| {
+                parse_field(&field.ty, &index.to_string(), quote! { __fields[#index] })
+            });
+            let body = quote! {
+                let mut __fields = [""; #count];
+                let mut __len = 0;
+                for __field in __split_fields(__inner) {
+                    if __len == #count {
+                        return ::core::result::Result::Err(#malformed);
+                    }
+                    __fields[__len] = __field;
+                    __len += 1;
+                }
+                if __len != #count {
+                    return ::core::result::Result::Err(#malformed);
+                }
+                return ::core::result::Result::Ok(#enum_name::#var_ident( #( #values ),* ));
+            };
+            ('(', body)
+        }
+        Fields::Unit => unreachable!(),
+    };
+
+    quote! {
+        #( #spellings )|* if __delim == #delim => {
+            let ::core::option::Option::Some(__inner) = __inner else {
+                return ::core::result::Result::Err(#malformed);
+            };
+            #body
+        }
+    }
+}
+
+// A match arm of `Display::fmt` that writes `variant` back in the syntax
+// `parse_arm` accepts, under its canonical spelling. Field values are written
+// as they format, so `Wrap("")` or `Pair("a,b", "c")` do not parse back.
+pub(crate) fn display_arm(enum_name: &Ident, variant: &Variant, canonical: &str) -> TokenStream {
+    let var_ident = &variant.ident;
+    match &variant.fields {
+        Fields::Named(named) => {
+            let field_idents = named.named.iter().map(|field| &field.ident).collect::<Vec<_>>();
+            let bindings = (0..field_idents.len()).map(|index| format_ident!("__field_{}", index)).collect::<Vec<_>>();
+            let labels = named.named.iter().enumerate().map(|(index, field)| {
+                let sep = if index == 0 { " " } else { ", " };
+                format!("{}{}: ", sep, field.ident.as_ref().unwrap().unraw())
+            });
+            let close = if field_idents.is_empty() { "}" } else { " }" };
+            quote! {
+                #enum_name::#var_ident { #( #field_idents: ref #bindings ),* } => {
+                    __f.write_str(#canonical)?;
+                    __f.write_str(" {")?;
+                    #(
+                        __f.write_str(#labels)?;
+                        ::core::fmt::Display::fmt(#bindings, __f)?;
+                    )*
+                    __f.write_str(#close)
+                }
+            }
+        }
+        Fields::Unnamed(unnamed) => {
+            let bindings = (0..unnamed.unnamed.len()).map(|index| format_ident!("__field_{}", index)).collect::<Vec<_>>();
+            let seps = (0..bindings.len()).map(|index| if index == 0 { "" } else { ", " });
+            quote! {
+                #enum_name::#var_ident( #( ref #bindings ),* ) => {
+                    __f.write_str(#canonical)?;
+                    __f.write_str("(")?;
+                    #(
+                        __f.write_str(#seps)?;
+                        ::core::fmt::Display::fmt(#bindings, __f)?;
+                    )*
+                    __f.write_str(")")
+                }
+            }
+        }
+        Fields::Unit => quote! {
+            #enum_name::#var_ident => __f.pad(#canonical),
+        },
+    }
+}
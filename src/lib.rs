@@ -1,145 +1,182 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{AttributeArgs, ItemEnum, Lit, Meta, NestedMeta, parse_macro_input};
+use syn::{Data, DeriveInput, parse_macro_input};
 
-#[proc_macro_attribute]
-pub fn derive_fromstr(attr: TokenStream, item: TokenStream) -> TokenStream {
-    // Parse attribute arguments as a list, e.g. [trim, lowercase]
-    let args = parse_macro_input!(attr as AttributeArgs);
-    let has_trim = args.iter().any(|arg| {
-        if let NestedMeta::Meta(Meta::Path(path)) = arg {
-            path.is_ident("trim")
-        } else {
-            false
-        }
-    });
-    let has_lowercase = args.iter().any(|arg| {
-        if let NestedMeta::Meta(Meta::Path(path)) = arg {
-            path.is_ident("lowercase")
-        } else {
-            false
-        }
-    });
-    // Parse truncate argument
-    let truncate_value: Option<usize> = args.iter().find_map(|arg| {
-        if let NestedMeta::Meta(Meta::List(meta_list)) = arg {
-            if meta_list.path.is_ident("truncate") && meta_list.nested.len() == 1 {
-                let first = meta_list.nested.first().unwrap();
-                if let NestedMeta::Lit(Lit::Int(lit_int)) = first {
-                    return Some(lit_int.base10_parse::<usize>().unwrap());
-                }
-            }
-        }
-        None
-    });
-
-    // Parse the input tokens into an enum
-    let input = parse_macro_input!(item as ItemEnum);
-    let enum_name = &input.ident;
-    let variants = &input.variants;
+mod case;
+mod expand;
+mod fields;
+mod options;
+mod structs;
 
-    // Check each variant to ensure it doesn't hold data
-    for variant in variants {
-        match variant.fields {
-            syn::Fields::Unit => {}
-            _ => {
-                return syn::Error::new_spanned(variant, concat!(env!("CARGO_PKG_NAME"), ": variants with data are not supported"))
-                    .to_compile_error()
-                    .into();
-            }
-        }
+/// Derives `FromStr` for an enum, or for a unit or newtype struct.
+///
+/// A unit variant parses from its name, and a variant with data from the way
+/// it is constructed, e.g. `Rgb(10, 20, 30)` or `Rect { w: 3, h: 4 }`, with
+/// each field going through its own `FromStr`. A unit struct parses from its
+/// name, and a newtype struct such as `struct Port(u16)` through its field.
+///
+/// ```
+/// use derive_fromstr::FromStr;
+///
+/// #[derive(FromStr, Debug, PartialEq)]
+/// #[fromstr(trim, lowercase, rename_all = "kebab-case")]
+/// enum Mode {
+///     FastForward,
+///     #[fromstr(alias = "rw")]
+///     Rewind,
+/// }
+///
+/// assert_eq!(" Fast-Forward ".parse(), Ok(Mode::FastForward));
+/// assert_eq!("RW".parse(), Ok(Mode::Rewind));
+/// assert!("play".parse::<Mode>().is_err());
+/// ```
+///
+/// # Options
+///
+/// Options for the whole type go in `#[fromstr(...)]` on it:
+///
+/// - `trim`: ignore surrounding whitespace. Blank input is an `Empty` error.
+/// - `lowercase`: match case-insensitively, by folding the input and every
+///   spelling to lowercase.
+/// - `truncate(N)`, `truncate(N, chars)` or `truncate(N, graphemes)`: also
+///   accept the first `N` chars or grapheme clusters of each unit variant's name.
+/// - `rename_all = "..."`: spell variant names in one of `lowercase`,
+///   `UPPERCASE`, `camelCase`, `PascalCase`, `snake_case`,
+///   `SCREAMING_SNAKE_CASE`, `kebab-case`, `SCREAMING-KEBAB-CASE` or `Title Case`.
+/// - `prefix` or `prefix(min = N)`: also accept an abbreviation of at least `N`
+///   (default 1) chars that starts only one unit variant's spellings.
+/// - `display`: also derive `Display`, writing each variant the way it parses.
+///   Fields are written unquoted, so a value only parses back when each
+///   field's own output does and is neither empty nor holds a top-level comma.
+/// - `discriminant` or `discriminant(hex)`: also accept a unit variant's
+///   discriminant in decimal, and with `hex` as `0x` hex.
+/// - `index`: also accept a unit variant's zero-based declaration index.
+/// - `max_distance = N`: suggest spellings within `N` edits of unknown input
+///   (default 2). `0` turns suggestions off.
+/// - `max_expected = N`: list at most `N` (at least 1) accepted spellings in the
+///   message of an unknown-variant error.
+/// - `error = Type`: return `Type` instead of generating an error type. See below.
+/// - `error_name = Name`: the name of the generated error type.
+/// - `error_vis = pub(crate)`: its visibility, the type's own by default.
+/// - `error_derive(Clone, Hash)`: more traits to derive for it.
+/// - `no_alloc`: generate code that never allocates, for `no_std` crates
+///   without `alloc`. Errors keep the byte range of the input instead of a copy.
+///
+/// `truncate`, `prefix`, `discriminant`, `index`, `max_distance` and
+/// `max_expected` apply to enums only.
+///
+/// Options for a single variant go in `#[fromstr(...)]` on the variant:
+///
+/// - `rename = "..."`: its spelling, in place of the one from `rename_all`.
+/// - `alias = "..."`: another accepted spelling. Can be given more than once.
+///   Both are folded by `lowercase` like the name, and under `trim` must be
+///   written without surrounding whitespace.
+/// - `prefix` or `prefix(min = N)`: accept abbreviations of this variant only.
+/// - `other`: the fallback for input no other variant matches. A unit variant
+///   keeps its own spelling too; a variant with a single field such as
+///   `String` captures the input.
+/// - `skip`: never parse this variant.
+///
+/// # Generated items
+///
+/// Besides the `FromStr` impl, an enum gets the associated items `NAMES`,
+/// `COUNT`, `VARIANTS` and `iter()`, which cover the unit variants that are
+/// not skipped, in declaration order.
+///
+/// Unless `error` is given, the type also gets an error enum
+/// `Parse{Name}Error`, which implements `Debug`, `PartialEq`, `Eq`, `Display`
+/// and `Error`. It has one variant per way parsing can fail, e.g.
+/// `UnknownVariant { input, expected, suggestions, .. }`, and only those that
+/// can happen for the type. A newtype struct's error has the single variant
+/// `Invalid`, which holds the field's error, and takes the struct's generic
+/// parameters; it is `Debug`, `Display`, `PartialEq`, `Eq` and an `Error`
+/// only when the field's error is, and then returns it as its `source`.
+///
+/// # Custom error types
+///
+/// With `error = Type`, every failure is built by calling an associated
+/// function of `Type` with the name of the type being parsed and the input as
+/// given:
+///
+/// ```
+/// # use derive_fromstr::FromStr;
+/// #[derive(Debug)]
+/// pub struct ConfigError {
+///     kind: &'static str,
+///     input: String,
+/// }
+///
+/// impl ConfigError {
+///     fn parse_error(kind: &'static str, input: &str) -> Self {
+///         ConfigError { kind, input: input.to_string() }
+///     }
+/// }
+///
+/// #[derive(FromStr)]
+/// #[fromstr(error = ConfigError)]
+/// enum Level {
+///     Low,
+///     High,
+/// }
+///
+/// let Err(err) = "Mid".parse::<Level>() else { unreachable!() };
+/// assert_eq!((err.kind, err.input.as_str()), ("Level", "Mid"));
+/// ```
+#[proc_macro_derive(FromStr, attributes(fromstr))]
+pub fn derive_from_str(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    match expand::expand(&input, &[]) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
     }
+}
 
-    // Generate match arms for each enum variant.
-    let mut arms_vec = variants
-        .iter()
-        .map(|variant| {
-            let var_ident = &variant.ident;
-            let var_name = var_ident.to_string();
-            let expected = if has_lowercase { var_name.to_lowercase() } else { var_name };
-            quote! {
-                #expected => Ok(#enum_name::#var_ident),
-            }
-        })
-        .collect::<Vec<_>>();
-
-    // Add extra match arms for truncated variant names if truncate_value is provided.
-    if let Some(trunc) = truncate_value {
-        for variant in variants {
-            let var_ident = &variant.ident;
-            let full_name = var_ident.to_string();
-            if full_name.len() > trunc {
-                let truncated = &full_name[..trunc];
-                let truncated = if has_lowercase {
-                    truncated.to_lowercase()
-                } else {
-                    truncated.to_string()
-                };
-                let original = if has_lowercase { full_name.to_lowercase() } else { full_name.clone() };
-                if truncated != original {
-                    arms_vec.push(quote! {
-                        #truncated => Ok(#enum_name::#var_ident),
-                    });
-                }
-            }
-        }
-    }
+/// The attribute form of [`derive@FromStr`], with the options for the whole
+/// type as its arguments:
+///
+/// ```
+/// use derive_fromstr::derive_fromstr;
+///
+/// #[derive_fromstr(trim, lowercase)]
+/// #[derive(Debug, PartialEq)]
+/// enum Level {
+///     Low,
+///     #[fromstr(alias = "hi")]
+///     High,
+/// }
+///
+/// assert_eq!(" HI ".parse(), Ok(Level::High));
+/// ```
+///
+/// It takes the same fifteen options, `trim`, `lowercase`, `truncate`,
+/// `rename_all`, `prefix`, `display`, `discriminant`, `index`,
+/// `max_distance`, `max_expected`, `error`, `error_name`, `error_vis`,
+/// `error_derive` and `no_alloc`, and generates the same items. Variant
+/// options still go in `#[fromstr(...)]`, which it removes from the output.
+// Compatibility shim over the same generator as `#[derive(FromStr)]`.
+#[proc_macro_attribute]
+pub fn derive_fromstr(attr: TokenStream, item: TokenStream) -> TokenStream {
+    // Parse attribute arguments as a list, e.g. [trim, lowercase]
+    let args = parse_macro_input!(attr with options::Arg::parse_list);
 
-    // Generate code to transform the input string based on flags.
-    let transform = if has_trim && has_lowercase {
-        quote! {
-            let temp = s.trim().to_lowercase();
-            let s = temp.as_str();
-        }
-    } else if has_trim {
-        quote! {
-            let s = s.trim();
-        }
-    } else if has_lowercase {
-        quote! {
-            let temp = s.to_lowercase();
-            let s = temp.as_str();
-        }
-    } else {
-        quote! {}
+    // Parse the input tokens into an enum or a struct
+    let mut input = parse_macro_input!(item as DeriveInput);
+    let generated = match expand::expand(&input, &args) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
     };
 
-    // Generate an error enum named Parse{EnumName}Error with required derives.
-    let error_enum_ident = syn::Ident::new(&format!("Parse{}Error", enum_name), enum_name.span());
-    let error_enum = quote! {
-        #[derive(Debug, PartialEq, Eq)]
-        pub enum #error_enum_ident {
-            UnknownVariant(String),
+    options::strip_helper_attrs(&mut input.attrs);
+    if let Data::Enum(data) = &mut input.data {
+        for variant in &mut data.variants {
+            options::strip_helper_attrs(&mut variant.attrs);
         }
-    };
+    }
 
-    // Generate the final tokens including the enum, error enum,
-    // and the FromStr implementation using the new error enum.
-    let gener = quote! {
+    quote! {
         #input
-        #error_enum
-        impl ::core::str::FromStr for #enum_name {
-            type Err = #error_enum_ident;
-            fn from_str(s: &str) -> Result<Self, Self::Err> {
-                #transform
-                match s {
-                    #( #arms_vec )*
-                    _ => Err(#error_enum_ident::UnknownVariant(s.to_string())),
-                }
-            }
-        }
-
-        impl ::core::error::Error for #error_enum_ident {}
-
-        impl ::core::fmt::Display for #error_enum_ident {
-            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                match self {
-                    #error_enum_ident::UnknownVariant(s) => write!(f, "Unknown variant: {}", s),
-                }
-            }
-        }
-    };
-
-    gener.into()
+        #generated
+    }
+    .into()
 }
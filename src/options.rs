@@ -0,0 +1,507 @@
+use std::fmt::Display;
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens;
+use unicode_segmentation::UnicodeSegmentation;
+use syn::parse::{Parse, ParseStream};
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Ident, Lit, LitInt, LitStr, Meta, NestedMeta, Path, Token, Type, TypePath, Visibility};
+
+use crate::case::RenameRule;
+
+// Name of the helper attribute shared by the derive and the attribute shim.
+pub(crate) const HELPER: &str = "fromstr";
+
+// A `syn::Error` spanned at `tokens` and prefixed with the crate name.
+pub(crate) fn error(tokens: impl ToTokens, message: impl Display) -> syn::Error {
+    syn::Error::new_spanned(tokens, format!("{}: {}", env!("CARGO_PKG_NAME"), message))
+}
+
+// Collects every problem found in the input so they are all reported in one
+// pass instead of one per compilation.
+#[derive(Default)]
+pub(crate) struct Errors(Option<syn::Error>);
+
+impl Errors {
+    pub(crate) fn push(&mut self, err: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(err),
+            None => self.0 = Some(err),
+        }
+    }
+
+    // Record the error of `result`, if any, and pass its value on.
+    pub(crate) fn check<T>(&mut self, result: syn::Result<T>) -> Option<T> {
+        result.map_err(|err| self.push(err)).ok()
+    }
+
+    pub(crate) fn finish(self) -> syn::Result<()> {
+        match self.0 {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+}
+
+// One argument of `#[derive_fromstr(...)]` or `#[fromstr(...)]`: any of the
+// usual `NestedMeta` forms, or `key = Type` and `key = pub(...)` for options
+// whose value is a type or a visibility, which `NestedMeta` cannot hold.
+#[derive(Clone)]
+pub(crate) enum Arg {
+    Meta(NestedMeta),
+    Type(Path, Box<Type>),
+    Vis(Path, Visibility),
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) && input.peek2(Token![=]) && !input.peek3(Lit) {
+            let path = Path::from(input.parse::<Ident>()?);
+            input.parse::<Token![=]>()?;
+            if input.peek(Token![pub]) {
+                return Ok(Arg::Vis(path, input.parse()?));
+            }
+            return Ok(Arg::Type(path, input.parse()?));
+        }
+        input.parse().map(Arg::Meta)
+    }
+}
+
+impl ToTokens for Arg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Arg::Meta(meta) => meta.to_tokens(tokens),
+            Arg::Type(path, ty) => {
+                path.to_tokens(tokens);
+                <Token![=]>::default().to_tokens(tokens);
+                ty.to_tokens(tokens);
+            }
+            Arg::Vis(path, vis) => {
+                path.to_tokens(tokens);
+                <Token![=]>::default().to_tokens(tokens);
+                vis.to_tokens(tokens);
+            }
+        }
+    }
+}
+
+impl Arg {
+    pub(crate) fn parse_list(input: ParseStream) -> syn::Result<Vec<Self>> {
+        Ok(Punctuated::<Arg, Token![,]>::parse_terminated(input)?.into_iter().collect())
+    }
+
+    // The option the argument sets, unless it is a bare literal.
+    fn path(&self) -> Option<&Path> {
+        match self {
+            Arg::Meta(NestedMeta::Meta(meta)) => Some(meta.path()),
+            Arg::Meta(NestedMeta::Lit(_)) => None,
+            Arg::Type(path, _) | Arg::Vis(path, _) => Some(path),
+        }
+    }
+
+    // The argument of an option whose value is not a type.
+    fn meta(&self) -> syn::Result<&NestedMeta> {
+        match self {
+            Arg::Meta(meta) => Ok(meta),
+            Arg::Type(path, ty) => {
+                Err(error(ty, format_args!("expected a literal value for `{}`", path.to_token_stream())))
+            }
+            Arg::Vis(path, vis) => {
+                Err(error(vis, format_args!("expected a literal value for `{}`", path.to_token_stream())))
+            }
+        }
+    }
+}
+
+// Enum-level options, e.g. `#[derive_fromstr(trim, lowercase, rename_all = "snake_case")]`
+// or `#[fromstr(trim, lowercase, truncate(3))]` next to `#[derive(FromStr)]`.
+#[derive(Default)]
+pub(crate) struct Options {
+    pub(crate) trim: bool,
+    pub(crate) lowercase: bool,
+    pub(crate) truncate: Option<Truncate>,
+    pub(crate) rename_all: Option<RenameRule>,
+    // Minimum abbreviation length when `prefix` is given.
+    pub(crate) prefix: Option<usize>,
+    pub(crate) display: bool,
+    // Also parse the discriminant as a decimal number, and as `0x` hex when `Some(true)`.
+    pub(crate) discriminant: Option<bool>,
+    // Also parse the zero-based declaration index.
+    pub(crate) index: bool,
+    // Largest edit distance of a "did you mean" suggestion; 0 turns them off.
+    pub(crate) max_distance: Option<usize>,
+    // How many accepted spellings an unknown-variant error lists.
+    pub(crate) max_expected: Option<usize>,
+    // User-supplied error type, built with `Type::parse_error(enum_name, input)`
+    // in place of a generated `Parse{EnumName}Error`.
+    pub(crate) error: Option<Type>,
+    // Name, visibility and extra derives of the generated error type.
+    pub(crate) error_name: Option<Ident>,
+    pub(crate) error_vis: Option<Visibility>,
+    pub(crate) error_derive: Vec<Path>,
+    // Generate code that never allocates, for `no_std` crates without `alloc`.
+    pub(crate) no_alloc: bool,
+}
+
+impl Options {
+    const KEYS: &'static [&'static str] = &[
+        "trim",
+        "lowercase",
+        "truncate",
+        "rename_all",
+        "prefix",
+        "display",
+        "discriminant",
+        "index",
+        "max_distance",
+        "max_expected",
+        "error",
+        "error_name",
+        "error_vis",
+        "error_derive",
+        "no_alloc",
+    ];
+
+    pub(crate) fn from_args(args: &[Arg], errors: &mut Errors) -> Self {
+        let mut opts = Options::default();
+        let mut seen = Vec::new();
+        for arg in args {
+            let Some(key) = errors.check(option_key(arg, Self::KEYS, &mut seen)) else {
+                continue;
+            };
+            match key.as_str() {
+                "trim" => opts.trim = errors.check(arg.meta().and_then(flag)).is_some(),
+                "lowercase" => opts.lowercase = errors.check(arg.meta().and_then(flag)).is_some(),
+                "truncate" => opts.truncate = errors.check(arg.meta().and_then(truncate)),
+                "rename_all" => opts.rename_all = errors.check(arg.meta().and_then(rename_all)),
+                "prefix" => opts.prefix = errors.check(arg.meta().and_then(prefix)),
+                "display" => opts.display = errors.check(arg.meta().and_then(flag)).is_some(),
+                "discriminant" => opts.discriminant = errors.check(arg.meta().and_then(discriminant)),
+                "index" => opts.index = errors.check(arg.meta().and_then(flag)).is_some(),
+                "max_distance" => opts.max_distance = errors.check(arg.meta().and_then(int_value)),
+                "max_expected" => opts.max_expected = errors.check(arg.meta().and_then(int_lit).and_then(positive)),
+                "error" => opts.error = errors.check(type_value(arg)),
+                "error_name" => opts.error_name = errors.check(ident_value(arg)),
+                "error_vis" => opts.error_vis = errors.check(vis_value(arg)),
+                "error_derive" => opts.error_derive = errors.check(path_list(arg)).unwrap_or_default(),
+                "no_alloc" => opts.no_alloc = errors.check(arg.meta().and_then(flag)).is_some(),
+                _ => unreachable!(),
+            }
+        }
+        opts
+    }
+
+    // The spelling a variant gets from its ident, before any case folding.
+    // `r#type` is spelled `type`.
+    pub(crate) fn variant_name(&self, ident: &syn::Ident) -> String {
+        let ident = ident.unraw().to_string();
+        match self.rename_all {
+            Some(rule) => rule.apply(&ident),
+            None => ident,
+        }
+    }
+
+    // Report the options in `args` that a struct has no use for: those about
+    // variants, and `rename_all` unless the struct is `named`, i.e. parses from
+    // its name.
+    pub(crate) fn check_struct(args: &[Arg], named: bool, errors: &mut Errors) {
+        for path in args.iter().filter_map(Arg::path) {
+            let key = path.get_ident().map(ToString::to_string).unwrap_or_default();
+            if ENUM_ONLY.contains(&key.as_str()) || (key == "rename_all" && !named) {
+                errors.push(error(path, format_args!("`{}` does not apply to a {} struct", key, if named { "unit" } else { "newtype" })));
+            }
+        }
+    }
+
+    // The value of a `rename` or `alias`. With `trim`, surrounding whitespace
+    // could never match; `lowercase` folds it later, like the variant name.
+    pub(crate) fn spelling(&self, lit: &LitStr) -> syn::Result<String> {
+        let value = lit.value();
+        if self.trim && value.trim() != value {
+            return Err(error(lit, format_args!("{:?} can never match: `trim` removes surrounding whitespace from the input", value)));
+        }
+        Ok(value)
+    }
+
+    pub(crate) fn max_distance(&self) -> usize {
+        self.max_distance.unwrap_or(2)
+    }
+
+    // Apply the same case folding to a spelling that `from_str` applies to its input.
+    pub(crate) fn normalize(&self, name: &str) -> String {
+        if self.lowercase { name.to_lowercase() } else { name.to_string() }
+    }
+}
+
+// `truncate(N)`: also accept the first `len` chars of a variant's name, or its
+// first `len` grapheme clusters with `graphemes`.
+#[derive(Clone, Copy)]
+pub(crate) struct Truncate {
+    pub(crate) len: usize,
+    pub(crate) graphemes: bool,
+}
+
+impl Truncate {
+    // The truncated form of `name`, if it is longer than `len`. Cutting through
+    // a grapheme cluster in char mode (`Cafe\u{301}` at 4) is an error, since
+    // the result would read as a different word.
+    pub(crate) fn apply<'a>(&self, name: &'a str) -> Result<Option<&'a str>, String> {
+        let end = if self.graphemes {
+            name.grapheme_indices(true).nth(self.len).map(|(end, _)| end)
+        } else {
+            name.char_indices().nth(self.len).map(|(end, _)| end)
+        };
+        let Some(end) = end else {
+            return Ok(None);
+        };
+        if !self.graphemes && !name.grapheme_indices(true).any(|(start, _)| start == end) {
+            return Err(format!(
+                "truncating {:?} to {} chars splits a grapheme cluster; use `truncate({}, graphemes)`",
+                name, self.len, self.len
+            ));
+        }
+        Ok(Some(&name[..end]))
+    }
+}
+
+// Variant-level options, e.g. `#[fromstr(rename = "x86-64", alias = "amd64", prefix(min = 3))]`
+// or `#[fromstr(other)]`.
+#[derive(Default)]
+pub(crate) struct VariantOptions {
+    pub(crate) rename: Option<String>,
+    pub(crate) aliases: Vec<String>,
+    pub(crate) prefix: Option<usize>,
+    // Catch-all for input no other variant matches.
+    pub(crate) other: bool,
+    // Never parsed, e.g. an internal state; still formatted by `display`.
+    pub(crate) skip: bool,
+}
+
+impl VariantOptions {
+    const KEYS: &'static [&'static str] = &["rename", "alias", "prefix", "other", "skip"];
+
+    // The options in `attrs`, with spellings checked against the enum's `opts`.
+    pub(crate) fn from_attrs(attrs: &[Attribute], enum_opts: &Options, errors: &mut Errors) -> Self {
+        let mut opts = VariantOptions::default();
+        let mut seen = Vec::new();
+        for arg in helper_args(attrs, errors) {
+            let Some(key) = errors.check(option_key(&arg, Self::KEYS, &mut seen)) else {
+                continue;
+            };
+            match key.as_str() {
+                "rename" => opts.rename = errors.check(arg.meta().and_then(string_value).and_then(|lit| enum_opts.spelling(&lit))),
+                "alias" => opts.aliases.extend(errors.check(arg.meta().and_then(string_value).and_then(|lit| enum_opts.spelling(&lit)))),
+                "prefix" => opts.prefix = errors.check(arg.meta().and_then(prefix)),
+                "other" => opts.other = errors.check(arg.meta().and_then(flag)).is_some(),
+                "skip" => opts.skip = errors.check(arg.meta().and_then(flag)).is_some(),
+                _ => unreachable!(),
+            }
+        }
+        opts
+    }
+}
+
+// Options about the variants of an enum.
+const ENUM_ONLY: &[&str] = &["truncate", "prefix", "discriminant", "index", "max_distance", "max_expected"];
+
+// Options that may be given more than once.
+const REPEATABLE: &[&str] = &["alias"];
+
+// The name of the option `arg` sets, which must be one of `keys` and, unless
+// repeatable, not in `seen` yet.
+fn option_key(arg: &Arg, keys: &[&str], seen: &mut Vec<String>) -> syn::Result<String> {
+    let Some(path) = arg.path() else {
+        return Err(error(arg, "expected an option, found a literal"));
+    };
+    let key = path.get_ident().map(ToString::to_string).unwrap_or_default();
+    if !keys.contains(&key.as_str()) {
+        let message = format!("unknown option `{}`, expected one of: {}", path.to_token_stream(), keys.join(", "));
+        return Err(error(path, message));
+    }
+    if seen.contains(&key) && !REPEATABLE.contains(&key.as_str()) {
+        return Err(error(path, format_args!("duplicate option `{}`", key)));
+    }
+    seen.push(key.clone());
+    Ok(key)
+}
+
+// `trim`
+fn flag(arg: &NestedMeta) -> syn::Result<()> {
+    match arg {
+        NestedMeta::Meta(Meta::Path(_)) => Ok(()),
+        _ => Err(error(arg, format_args!("`{}` takes no value", arg_path(arg)))),
+    }
+}
+
+// `rename = "..."`
+fn string_value(arg: &NestedMeta) -> syn::Result<LitStr> {
+    match arg {
+        NestedMeta::Meta(Meta::NameValue(name_value)) => match &name_value.lit {
+            Lit::Str(lit_str) => Ok(lit_str.clone()),
+            lit => Err(error(lit, "expected a string literal")),
+        },
+        _ => Err(error(arg, format_args!("expected `{} = \"...\"`", arg_path(arg)))),
+    }
+}
+
+// `error = path::ToError`
+fn type_value(arg: &Arg) -> syn::Result<Type> {
+    match arg {
+        Arg::Type(_, ty) => Ok((**ty).clone()),
+        Arg::Meta(meta) => Err(error(meta, format_args!("expected `{} = Type`", arg_path(meta)))),
+        Arg::Vis(path, vis) => Err(error(vis, format_args!("expected `{} = Type`", path.to_token_stream()))),
+    }
+}
+
+// `error_name = ParseError` or `error_name = "ParseError"`
+fn ident_value(arg: &Arg) -> syn::Result<Ident> {
+    match arg {
+        Arg::Type(_, ty) => match &**ty {
+            Type::Path(TypePath { qself: None, path }) if path.get_ident().is_some() => Ok(path.get_ident().unwrap().clone()),
+            ty => Err(error(ty, "expected an identifier")),
+        },
+        Arg::Meta(meta) => {
+            let lit_str = string_value(meta)?;
+            lit_str.parse().map_err(|_| error(&lit_str, "expected an identifier"))
+        }
+        Arg::Vis(path, vis) => Err(error(vis, format_args!("expected `{} = Ident`", path.to_token_stream()))),
+    }
+}
+
+// `error_vis = pub(crate)`, or `error_vis = "pub(crate)"` and `error_vis = ""` for private
+fn vis_value(arg: &Arg) -> syn::Result<Visibility> {
+    match arg {
+        Arg::Vis(_, vis) => Ok(vis.clone()),
+        Arg::Meta(meta) => {
+            let lit_str = string_value(meta)?;
+            lit_str.parse().map_err(|_| error(&lit_str, "expected a visibility such as `pub(crate)`"))
+        }
+        Arg::Type(path, ty) => Err(error(ty, format_args!("expected `{} = pub(...)`", path.to_token_stream()))),
+    }
+}
+
+// `error_derive(Clone, Hash)`
+fn path_list(arg: &Arg) -> syn::Result<Vec<Path>> {
+    match arg {
+        Arg::Meta(NestedMeta::Meta(Meta::List(meta_list))) => meta_list
+            .nested
+            .iter()
+            .map(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => Ok(path.clone()),
+                nested => Err(error(nested, "expected a trait to derive")),
+            })
+            .collect(),
+        _ => Err(error(arg, "expected `error_derive(Trait, ...)`")),
+    }
+}
+
+// `max_distance = N`
+fn int_value(arg: &NestedMeta) -> syn::Result<usize> {
+    let lit_int = int_lit(arg)?;
+    lit_int.base10_parse::<usize>().map_err(|err| error(lit_int, err))
+}
+
+// The literal of `max_distance = N` or `max_expected = N`
+fn int_lit(arg: &NestedMeta) -> syn::Result<&LitInt> {
+    match arg {
+        NestedMeta::Meta(Meta::NameValue(name_value)) => match &name_value.lit {
+            Lit::Int(lit_int) => Ok(lit_int),
+            lit => Err(error(lit, "expected an integer literal")),
+        },
+        _ => Err(error(arg, format_args!("expected `{} = N`", arg_path(arg)))),
+    }
+}
+
+// `truncate(N)` with N > 0, optionally `truncate(N, chars)` or `truncate(N, graphemes)`
+fn truncate(arg: &NestedMeta) -> syn::Result<Truncate> {
+    let NestedMeta::Meta(Meta::List(meta_list)) = arg else {
+        return Err(error(arg, "expected `truncate(N)` or `truncate(N, graphemes)`"));
+    };
+    let mut nested = meta_list.nested.iter();
+    let len = match nested.next() {
+        Some(NestedMeta::Lit(Lit::Int(lit_int))) => positive(lit_int)?,
+        Some(nested) => return Err(error(nested, "expected an integer literal")),
+        None => return Err(error(arg, "expected `truncate(N)` or `truncate(N, graphemes)`")),
+    };
+    let graphemes = match nested.next() {
+        None => false,
+        Some(NestedMeta::Meta(Meta::Path(path))) if path.is_ident("chars") => false,
+        Some(NestedMeta::Meta(Meta::Path(path))) if path.is_ident("graphemes") => true,
+        Some(nested) => return Err(error(nested, "expected `chars` or `graphemes`")),
+    };
+    if let Some(nested) = nested.next() {
+        return Err(error(nested, "unexpected argument"));
+    }
+    Ok(Truncate { len, graphemes })
+}
+
+// `rename_all = "snake_case"`
+fn rename_all(arg: &NestedMeta) -> syn::Result<RenameRule> {
+    let lit_str = string_value(arg)?;
+    RenameRule::from_name(&lit_str.value()).ok_or_else(|| {
+        let message = format!("unknown `rename_all` convention, expected one of: {}", RenameRule::NAMES.join(", "));
+        error(&lit_str, message)
+    })
+}
+
+// `prefix` or `prefix(min = N)` with N > 0, parsed into the minimum abbreviation length.
+fn prefix(arg: &NestedMeta) -> syn::Result<usize> {
+    match arg {
+        NestedMeta::Meta(Meta::Path(_)) => Ok(1),
+        NestedMeta::Meta(Meta::List(meta_list)) if meta_list.nested.len() == 1 => match &meta_list.nested[0] {
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("min") => match &name_value.lit {
+                Lit::Int(lit_int) => positive(lit_int),
+                lit => Err(error(lit, "expected an integer literal")),
+            },
+            nested => Err(error(nested, "expected `min = N`")),
+        },
+        _ => Err(error(arg, "expected `prefix` or `prefix(min = N)`")),
+    }
+}
+
+// `discriminant` or `discriminant(hex)`, parsed into whether hex is accepted too.
+fn discriminant(arg: &NestedMeta) -> syn::Result<bool> {
+    match arg {
+        NestedMeta::Meta(Meta::Path(_)) => Ok(false),
+        NestedMeta::Meta(Meta::List(meta_list)) if meta_list.nested.len() == 1 => match &meta_list.nested[0] {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("hex") => Ok(true),
+            nested => Err(error(nested, "expected `hex`")),
+        },
+        _ => Err(error(arg, "expected `discriminant` or `discriminant(hex)`")),
+    }
+}
+
+fn positive(lit_int: &LitInt) -> syn::Result<usize> {
+    match lit_int.base10_parse::<usize>().map_err(|err| error(lit_int, err))? {
+        0 => Err(error(lit_int, "expected a value greater than zero")),
+        value => Ok(value),
+    }
+}
+
+fn arg_path(arg: &NestedMeta) -> String {
+    match arg {
+        NestedMeta::Meta(meta) => meta.path().to_token_stream().to_string(),
+        NestedMeta::Lit(lit) => lit.to_token_stream().to_string(),
+    }
+}
+
+// Collect the arguments of every `#[fromstr(...)]` attribute in `attrs`.
+pub(crate) fn helper_args(attrs: &[Attribute], errors: &mut Errors) -> Vec<Arg> {
+    let mut args = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident(HELPER)) {
+        match attr.tokens.clone().into_iter().next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                args.extend(errors.check(attr.parse_args_with(Arg::parse_list)).into_iter().flatten());
+            }
+            _ => errors.push(error(attr, "expected `#[fromstr(...)]`")),
+        }
+    }
+    args
+}
+
+// The attribute shim re-emits the item itself, so the helper attributes
+// have to be removed: no derive registers them there.
+pub(crate) fn strip_helper_attrs(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| !attr.path.is_ident(HELPER));
+}
@@ -0,0 +1,217 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{DataStruct, DeriveInput, Fields};
+
+use crate::expand::{self, ErrorType};
+use crate::options::{Arg, Errors, Options, error};
+
+// Structs: a unit struct parses from its name, and a newtype struct such as
+// `struct Port(u16)` through the `FromStr` of its field, with the field's
+// error wrapped in `Parse{Name}Error`. `trim` and `lowercase` apply to the
+// input before either.
+pub(crate) fn expand(input: &DeriveInput, data: &DataStruct, args: &[Arg], opts: &Options, mut errors: Errors) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let field = match &data.fields {
+        Fields::Unit => None,
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => unnamed.unnamed.first(),
+        _ => {
+            errors.push(error(name, "only unit structs and newtype structs such as `struct Port(u16)` are supported"));
+            None
+        }
+    };
+    Options::check_struct(args, field.is_none(), &mut errors);
+
+    let error_enum_ident = expand::error_ident(input, opts);
+    let error_type = match &opts.error {
+        Some(ty) => ErrorType::Custom(ty, name.unraw().to_string()),
+        None => ErrorType::Generated(&error_enum_ident),
+    };
+    let type_params = input.generics.type_params().map(|param| &param.ident).collect::<Vec<_>>();
+    if field.is_some() && opts.lowercase && opts.no_alloc {
+        errors.push(error(name, "`lowercase` on a newtype struct needs `alloc` to fold input of any length"));
+    }
+    errors.finish()?;
+
+    let cfgs = expand::type_cfgs(input);
+    let (alloc, alloc_crate) = expand::alloc_crate(input, opts);
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let trim = if opts.trim {
+        quote! {
+            let __s = __s.trim();
+        }
+    } else {
+        quote! {}
+    };
+    let mut error_variants = Vec::new();
+    let mut error_display = Vec::new();
+    let mut from_str_bounds = Vec::new();
+    let mut display_bounds = Vec::new();
+
+    let (body, display) = match field {
+        // Blank input is left for the field to accept or reject, so `trim`
+        // adds no error of its own.
+        Some(field) => {
+            let ty = &field.ty;
+            if expand::mentions_any(ty, &type_params) {
+                from_str_bounds.push(quote! { #ty: ::core::str::FromStr });
+                display_bounds.push(quote! { #ty: ::core::fmt::Display });
+            }
+            let input = match &error_type {
+                ErrorType::Custom(..) => quote! {
+                    let __input = __s;
+                },
+                ErrorType::Generated(_) => quote! {},
+            };
+            let lowercase = if opts.lowercase {
+                expand::to_lowercase(&format_ident!("__s"), alloc.as_ref(), 0)
+            } else {
+                quote! {}
+            };
+            let err = if error_type.is_generated() { quote! { __err } } else { quote! { _ } };
+            let invalid = error_type.build(quote! { Invalid(__err) });
+            error_variants.push(quote! {
+                /// The field failed to parse.
+                Invalid(<#ty as ::core::str::FromStr>::Err),
+            });
+            let bare_name = name.unraw().to_string();
+            error_display.push(quote! {
+                #error_enum_ident::Invalid(ref __error) => ::core::write!(__f, "Invalid {}: {}", #bare_name, __error),
+            });
+            let body = quote! {
+                #input
+                #trim
+                #lowercase
+                match <#ty as ::core::str::FromStr>::from_str(__s) {
+                    ::core::result::Result::Ok(__value) => ::core::result::Result::Ok(#name(__value)),
+                    ::core::result::Result::Err(#err) => ::core::result::Result::Err(#invalid),
+                }
+            };
+            let display = quote! { ::core::fmt::Display::fmt(&self.0, __f) };
+            (body, display)
+        }
+        None => {
+            let expected = opts.normalize(&opts.variant_name(name));
+            let span = expand::input_span(opts);
+            let raw = if opts.trim && error_type.is_generated() {
+                quote! {
+                    let __raw = __s;
+                }
+            } else {
+                quote! {}
+            };
+            let empty_check = if opts.trim {
+                error_variants.push(quote! {
+                    /// The input was empty or all whitespace.
+                    Empty,
+                });
+                error_display.push(quote! {
+                    #error_enum_ident::Empty => __f.write_str("Empty input"),
+                });
+                let empty = error_type.build(quote! { Empty });
+                quote! {
+                    if __s.is_empty() {
+                        return ::core::result::Result::Err(#empty);
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            let lowercase = if opts.lowercase {
+                expand::to_lowercase(&format_ident!("__s"), alloc.as_ref(), expected.len())
+            } else {
+                quote! {}
+            };
+            let mismatch = match &alloc {
+                Some(alloc) => {
+                    error_variants.push(quote! {
+                        /// The input was not the struct's name.
+                        Mismatch {
+                            /// The input as given.
+                            input: #alloc::string::String,
+                            /// The byte range of the input that was compared.
+                            span: ::core::ops::Range<::core::primitive::usize>,
+                            /// The name the struct parses from.
+                            expected: &'static ::core::primitive::str,
+                        },
+                    });
+                    error_display.push(quote! {
+                        #error_enum_ident::Mismatch { input: ref __input, expected: __expected, .. } => {
+                            ::core::write!(__f, "Expected {:?}, found {:?}", __expected, __input)
+                        }
+                    });
+                    quote! { Mismatch { input: #alloc::string::ToString::to_string(__input), span: #span, expected: #expected } }
+                }
+                None => {
+                    error_variants.push(quote! {
+                        /// The input was not the struct's name.
+                        Mismatch {
+                            /// The byte range of the input that was compared.
+                            span: ::core::ops::Range<::core::primitive::usize>,
+                            /// The name the struct parses from.
+                            expected: &'static ::core::primitive::str,
+                        },
+                    });
+                    error_display.push(quote! {
+                        #error_enum_ident::Mismatch { span: ref __span, expected: __expected } => {
+                            ::core::write!(__f, "Expected {:?} at {}..{}", __expected, __span.start, __span.end)
+                        }
+                    });
+                    quote! { Mismatch { span: #span, expected: #expected } }
+                }
+            };
+            let mismatch = error_type.build(mismatch);
+            let body = quote! {
+                let __input = __s;
+                #trim
+                #raw
+                #empty_check
+                #lowercase
+                if __s == #expected {
+                    return ::core::result::Result::Ok(#name);
+                }
+                ::core::result::Result::Err(#mismatch)
+            };
+            let display = quote! { __f.pad(#expected) };
+            (body, display)
+        }
+    };
+
+    // With `display`, a unit struct formats as the name it parses from, and a
+    // newtype as its field.
+    let display = if opts.display {
+        let display_where_clause = expand::bounded_where_clause(&input.generics, &display_bounds);
+        quote! {
+            #( #cfgs )*
+            impl #impl_generics ::core::fmt::Display for #name #ty_generics #display_where_clause {
+                fn fmt(&self, __f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #display
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let error_enum = expand::error_enum(input, opts, &error_enum_ident, &error_variants, &error_display, field.map(|field| &field.ty));
+    // A newtype's generated error takes the struct's parameters.
+    let err_type = match error_type {
+        ErrorType::Generated(ident) if field.is_some() => quote! { #ident #ty_generics },
+        _ => error_type.ty(),
+    };
+    let constructor = error_type.constructor();
+    let from_str_where_clause = expand::bounded_where_clause(&input.generics, &from_str_bounds);
+
+    Ok(quote! {
+        #alloc_crate
+        #display
+        #error_enum
+        #( #cfgs )*
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #from_str_where_clause {
+            type Err = #err_type;
+            fn from_str(__s: &::core::primitive::str) -> ::core::result::Result<Self, <Self as ::core::str::FromStr>::Err> {
+                #constructor
+                #body
+            }
+        }
+    })
+}
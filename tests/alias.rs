@@ -0,0 +1,34 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+enum Color {
+    #[fromstr(alias = "Grey", alias = "Gris")]
+    Gray,
+    Red,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase)]
+enum Cmd {
+    #[fromstr(rename = "Remove", alias = "RM", alias = "Del")]
+    Remove,
+    Add,
+}
+
+#[test]
+fn every_alias_parses() {
+    assert_eq!("Gray".parse::<Color>(), Ok(Color::Gray));
+    assert_eq!("Grey".parse::<Color>(), Ok(Color::Gray));
+    assert_eq!("Gris".parse::<Color>(), Ok(Color::Gray));
+    assert!("grey".parse::<Color>().is_err());
+}
+
+#[test]
+fn aliases_are_case_folded_like_the_name() {
+    assert_eq!("rm".parse::<Cmd>(), Ok(Cmd::Remove));
+    assert_eq!("RM".parse::<Cmd>(), Ok(Cmd::Remove));
+    assert_eq!("del".parse::<Cmd>(), Ok(Cmd::Remove));
+    assert_eq!("dEL".parse::<Cmd>(), Ok(Cmd::Remove));
+    assert_eq!("remove".parse::<Cmd>(), Ok(Cmd::Remove));
+    assert_eq!("add".parse::<Cmd>(), Ok(Cmd::Add));
+}
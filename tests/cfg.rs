@@ -0,0 +1,87 @@
+use derive_fromstr::derive_fromstr;
+
+// `cfg(any())` is never set and `cfg(test)` always is here. The derive only sees
+// the variants that are compiled in, so this is about the attribute form.
+
+#[derive_fromstr(lowercase, truncate(3), prefix, display)]
+#[derive(Debug, PartialEq)]
+enum Backend {
+    Memory,
+    #[cfg(any())]
+    #[fromstr(alias = "pg")]
+    Postgres,
+    #[cfg_attr(test, cfg(test))]
+    Sqlite,
+    #[cfg(any())]
+    Remote(String),
+    #[cfg(test)]
+    File(String),
+}
+
+#[derive_fromstr(discriminant)]
+#[derive(Debug, PartialEq)]
+enum Level {
+    Low = 1,
+    High = 3,
+    // A number only parses if it does not depend on this one.
+    #[cfg(any())]
+    Extra = 7,
+}
+
+// Only one of each pair is ever compiled in.
+#[derive_fromstr(lowercase, prefix, discriminant)]
+#[derive(Debug, PartialEq)]
+enum Platform {
+    #[cfg(test)]
+    #[fromstr(rename = "native")]
+    Unix = 1,
+    #[cfg(not(test))]
+    #[fromstr(rename = "native")]
+    Windows = 1,
+    Web = 2,
+}
+
+#[cfg(any())]
+#[derive_fromstr]
+enum Absent {
+    Nothing,
+}
+
+#[test]
+fn gated_out_variants_do_not_parse() {
+    assert_eq!("memory".parse::<Backend>(), Ok(Backend::Memory));
+    assert_eq!("sqlite".parse::<Backend>(), Ok(Backend::Sqlite));
+    assert_eq!("file(a.db)".parse::<Backend>(), Ok(Backend::File("a.db".into())));
+    for input in ["postgres", "pg", "pos", "remote(x)"] {
+        assert!(input.parse::<Backend>().is_err(), "{} parsed", input);
+    }
+    // Only `sqlite` abbreviates to "s" once `postgres` is gone.
+    assert_eq!("s".parse::<Backend>(), Ok(Backend::Sqlite));
+}
+
+#[test]
+fn gated_out_variants_are_not_listed() {
+    assert_eq!(Backend::NAMES, ["memory", "sqlite"]);
+    assert_eq!(Backend::VARIANTS, [Backend::Memory, Backend::Sqlite]);
+    let Err(ParseBackendError::UnknownVariant { expected, suggestions, .. }) = "postgre".parse::<Backend>() else {
+        panic!("\"postgre\" parsed");
+    };
+    assert_eq!(expected, ["memory", "sqlite", "file(..)"]);
+    assert!(suggestions.is_empty());
+}
+
+#[test]
+fn numbers_before_a_gated_variant() {
+    assert_eq!("3".parse::<Level>(), Ok(Level::High));
+    assert_eq!("1".parse::<Level>(), Ok(Level::Low));
+    assert!("7".parse::<Level>().is_err());
+    assert_eq!(Level::COUNT, 2);
+}
+
+#[test]
+fn spellings_of_exclusive_variants_can_be_shared() {
+    assert_eq!("Native".parse::<Platform>(), Ok(Platform::Unix));
+    assert_eq!("nat".parse::<Platform>(), Ok(Platform::Unix));
+    assert_eq!("1".parse::<Platform>(), Ok(Platform::Unix));
+    assert_eq!(Platform::NAMES, ["native", "web"]);
+}
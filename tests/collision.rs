@@ -0,0 +1,16 @@
+use derive_fromstr::FromStr;
+
+// A variant may produce the same spelling more than once.
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase, truncate(3))]
+enum Short {
+    #[fromstr(alias = "red")]
+    Red,
+    Blue,
+}
+
+#[test]
+fn repeated_spellings_of_one_variant_are_allowed() {
+    assert_eq!("RED".parse::<Short>(), Ok(Short::Red));
+    assert_eq!("blu".parse::<Short>(), Ok(Short::Blue));
+}
@@ -0,0 +1,65 @@
+use derive_fromstr::{FromStr, derive_fromstr};
+
+#[derive(Debug, PartialEq)]
+pub struct ConfigError {
+    kind: &'static str,
+    input: String,
+}
+
+impl ConfigError {
+    fn parse_error(kind: &'static str, input: &str) -> Self {
+        ConfigError { kind, input: input.to_string() }
+    }
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(error = ConfigError, trim, lowercase, prefix)]
+enum Mode {
+    Fast,
+    Slow,
+    Stop,
+    Pair(u8, u8),
+}
+
+#[derive_fromstr(error = crate::ConfigError)]
+#[derive(Debug, PartialEq)]
+enum r#Level {
+    Low,
+    High,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(error = ConfigError)]
+struct Port(u16);
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(error = ConfigError, trim)]
+struct Auto;
+
+fn err(kind: &'static str, input: &str) -> ConfigError {
+    ConfigError { kind, input: input.to_string() }
+}
+
+#[test]
+fn every_failure_goes_through_parse_error() {
+    assert_eq!(" FAST ".parse::<Mode>(), Ok(Mode::Fast));
+    // Unknown, ambiguous, empty and malformed input alike.
+    assert_eq!(" Medium ".parse::<Mode>(), Err(err("Mode", " Medium ")));
+    assert_eq!("s".parse::<Mode>(), Err(err("Mode", "s")));
+    assert_eq!("  ".parse::<Mode>(), Err(err("Mode", "  ")));
+    assert_eq!("pair(1, x)".parse::<Mode>(), Err(err("Mode", "pair(1, x)")));
+}
+
+#[test]
+fn raw_names_are_passed_bare() {
+    assert_eq!("High".parse::<Level>(), Ok(Level::High));
+    assert_eq!("Mid".parse::<Level>(), Err(err("Level", "Mid")));
+}
+
+#[test]
+fn structs_use_it_too() {
+    assert_eq!("80".parse::<Port>(), Ok(Port(80)));
+    assert_eq!("eighty".parse::<Port>(), Err(err("Port", "eighty")));
+    assert_eq!(" Auto ".parse::<Auto>(), Ok(Auto));
+    assert_eq!(" Manual ".parse::<Auto>(), Err(err("Auto", " Manual ")));
+}
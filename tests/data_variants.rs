@@ -0,0 +1,62 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, lowercase)]
+enum Shape {
+    Dot,
+    Circle(f64),
+    Rgb(u8, u8, u8),
+    Rect { w: u32, h: u32 },
+    Empty {},
+    Nested(Inner),
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+enum Inner {
+    Pair(u8, u8),
+}
+
+#[test]
+fn tuple_variants() {
+    assert_eq!("Circle(1.5)".parse::<Shape>(), Ok(Shape::Circle(1.5)));
+    assert_eq!(" rgb( 10, 20 ,30 ) ".parse::<Shape>(), Ok(Shape::Rgb(10, 20, 30)));
+    assert_eq!("Rgb(1, 2, 3,)".parse::<Shape>(), Ok(Shape::Rgb(1, 2, 3)));
+}
+
+#[test]
+fn struct_variants_take_fields_in_any_order() {
+    assert_eq!("Rect { w: 3, h: 4 }".parse::<Shape>(), Ok(Shape::Rect { w: 3, h: 4 }));
+    assert_eq!("rect{h:4,w:3}".parse::<Shape>(), Ok(Shape::Rect { w: 3, h: 4 }));
+    assert_eq!("Empty {}".parse::<Shape>(), Ok(Shape::Empty {}));
+}
+
+#[test]
+fn nested_brackets_stay_in_one_field() {
+    assert_eq!("Nested(Pair(1, 2))".parse::<Shape>(), Ok(Shape::Nested(Inner::Pair(1, 2))));
+}
+
+#[test]
+fn field_errors_name_the_variant_and_field() {
+    let err = "Rect { w: 3, h: x }".parse::<Shape>().unwrap_err();
+    assert_eq!(err, ParseShapeError::InvalidField { variant: "rect", field: "h", error: "invalid digit found in string".into() });
+    assert_eq!(err.to_string(), "Invalid field h of rect: invalid digit found in string");
+    assert!(matches!("Rgb(1, 2, 300)".parse::<Shape>(), Err(ParseShapeError::InvalidField { field: "2", .. })));
+}
+
+#[test]
+fn malformed_calls() {
+    for input in ["Rgb(1, 2)", "Rgb(1, 2, 3, 4)", "Rgb(1, 2, 3", "Rect { w: 3 }", "Rect { w: 3, w: 3, h: 4 }", "Rect { w: 3, d: 4 }", "Empty { x: 1 }"] {
+        assert!(matches!(input.parse::<Shape>(), Err(ParseShapeError::MalformedVariant { .. })), "{}", input);
+    }
+    // The other delimiter does not name the variant at all.
+    assert!(matches!("Rect(3, 4)".parse::<Shape>(), Err(ParseShapeError::UnknownVariant { .. })));
+    let err = "Rgb(1)".parse::<Shape>().unwrap_err();
+    assert_eq!(err, ParseShapeError::MalformedVariant { variant: "rgb", input: "Rgb(1)".into() });
+    assert_eq!(err.to_string(), "Malformed rgb: Rgb(1)");
+}
+
+#[test]
+fn unknown_names_list_the_call_forms() {
+    let err = "Square(1)".parse::<Shape>().unwrap_err();
+    assert!(matches!(err, ParseShapeError::UnknownVariant { expected, .. } if expected == ["dot", "circle(..)", "rgb(..)", "rect { .. }", "empty { .. }", "nested(..)"]));
+}
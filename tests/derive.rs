@@ -0,0 +1,53 @@
+use derive_fromstr::{FromStr, derive_fromstr};
+
+#[derive(FromStr, Debug, PartialEq)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive_fromstr]
+#[derive(Debug, PartialEq)]
+enum Shim {
+    Red,
+    Green,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase)]
+enum Helper {
+    Red,
+    Green,
+}
+
+#[derive_fromstr(trim)]
+#[derive(Debug, PartialEq)]
+#[fromstr(lowercase)]
+enum Both {
+    Red,
+    Green,
+}
+
+#[test]
+fn derive_parses_variant_names() {
+    assert_eq!("Red".parse::<Color>(), Ok(Color::Red));
+    assert_eq!("Blue".parse::<Color>(), Ok(Color::Blue));
+    assert!(matches!("red".parse::<Color>(), Err(ParseColorError::UnknownVariant { .. })));
+}
+
+#[test]
+fn attribute_shim_generates_the_same_impl() {
+    assert_eq!("Green".parse::<Shim>(), Ok(Shim::Green));
+    assert!(matches!("Blue".parse::<Shim>(), Err(ParseShimError::UnknownVariant { .. })));
+}
+
+#[test]
+fn helper_attribute_sets_options() {
+    assert_eq!("GREEN".parse::<Helper>(), Ok(Helper::Green));
+}
+
+#[test]
+fn shim_arguments_combine_with_helper_attribute() {
+    assert_eq!("  RED ".parse::<Both>(), Ok(Both::Red));
+}
@@ -0,0 +1,60 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq, Clone, Copy)]
+#[fromstr(display, rename_all = "kebab-case")]
+enum Color {
+    DarkBlue,
+    #[fromstr(rename = "x86-64", alias = "amd64")]
+    X86_64,
+    Red,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(display, lowercase)]
+enum Shape {
+    Dot,
+    Circle(f64),
+    Rect { w: u32, h: u32 },
+    Wrap(String),
+}
+
+#[test]
+fn as_str_is_the_canonical_spelling() {
+    assert_eq!(Color::DarkBlue.as_str(), "dark-blue");
+    assert_eq!(Color::X86_64.as_str(), "x86-64");
+    const RED: &str = Color::Red.as_str();
+    assert_eq!(RED, "red");
+}
+
+#[test]
+fn unit_variants_round_trip() {
+    for color in [Color::DarkBlue, Color::X86_64, Color::Red] {
+        assert_eq!(color.to_string().parse::<Color>(), Ok(color));
+    }
+    assert_eq!("amd64".parse::<Color>().unwrap().to_string(), "x86-64");
+}
+
+#[test]
+fn unit_variants_respect_width_and_fill() {
+    assert_eq!(format!("[{:>8}]", Color::Red), "[     red]");
+    assert_eq!(format!("[{:-<6}]", Shape::Dot), "[dot---]");
+    assert_eq!(format!("[{:.2}]", Color::DarkBlue), "[da]");
+}
+
+#[test]
+fn data_variants_format_in_call_syntax() {
+    assert_eq!(Shape::Circle(1.5).to_string(), "circle(1.5)");
+    assert_eq!(Shape::Rect { w: 3, h: 4 }.to_string(), "rect { w: 3, h: 4 }");
+    for shape in [Shape::Dot, Shape::Circle(2.0), Shape::Rect { w: 1, h: 2 }, Shape::Wrap("x".into())] {
+        assert_eq!(shape.to_string().parse::<Shape>(), Ok(shape));
+    }
+}
+
+#[test]
+fn fields_are_written_unquoted() {
+    // Field values are written unquoted, so neither of these parses back.
+    assert_eq!(Shape::Wrap(String::new()).to_string(), "wrap()");
+    assert_eq!(Shape::Wrap("a,b".into()).to_string(), "wrap(a,b)");
+    assert!("wrap()".parse::<Shape>().is_err());
+    assert!("wrap(a,b)".parse::<Shape>().is_err());
+}
@@ -0,0 +1,63 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, lowercase, prefix)]
+enum Color {
+    Red,
+    Green,
+    Grey,
+    Blue,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(max_expected = 2)]
+enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+#[test]
+fn blank_input_is_empty() {
+    assert_eq!("".parse::<Color>(), Err(ParseColorError::Empty));
+    assert_eq!(" \t".parse::<Color>(), Err(ParseColorError::Empty));
+    assert_eq!(ParseColorError::Empty.to_string(), "Empty input");
+}
+
+#[test]
+fn shared_prefix_is_ambiguous() {
+    let Err(ParseColorError::Ambiguous { input, candidates, .. }) = "gr".parse::<Color>() else {
+        panic!("\"gr\" is not ambiguous");
+    };
+    assert_eq!(input, "gr");
+    assert_eq!(candidates, ["green", "grey"]);
+    assert_eq!("gr".parse::<Color>().unwrap_err().to_string(), "Ambiguous variant \"gr\" (could be green, grey)");
+}
+
+#[test]
+fn long_input_is_too_long() {
+    // The longest spelling plus `max_distance` chars is the most worth comparing.
+    assert!(matches!("greenis".parse::<Color>(), Err(ParseColorError::UnknownVariant { .. })));
+    let err = "greenish".parse::<Color>().unwrap_err();
+    assert_eq!(err, ParseColorError::TooLong { len: 8, max: 5 });
+    assert_eq!(err.to_string(), "Input too long: 8 characters, the longest variant has 5");
+}
+
+#[test]
+fn unknown_variant_lists_expected() {
+    let Err(ParseColorError::UnknownVariant { input, expected, .. }) = "pink".parse::<Color>() else {
+        panic!("\"pink\" parsed");
+    };
+    assert_eq!(input, "pink");
+    assert_eq!(expected, ["red", "green", "grey", "blue"]);
+    assert_eq!("pink".parse::<Color>().unwrap_err().to_string(), "Unknown variant \"pink\" (expected one of: red, green, grey, blue)");
+}
+
+#[test]
+fn max_expected_truncates_the_list() {
+    let err = "Fatal".parse::<Level>().unwrap_err();
+    assert!(matches!(&err, ParseLevelError::UnknownVariant { expected, .. } if expected.len() == 5));
+    assert_eq!(err.to_string(), "Unknown variant \"Fatal\" (expected one of: Trace, Debug and 3 more)");
+}
@@ -0,0 +1,50 @@
+use std::collections::HashSet;
+
+use derive_fromstr::FromStr;
+
+mod config {
+    use derive_fromstr::{FromStr, derive_fromstr};
+
+    #[derive(FromStr, Debug, PartialEq)]
+    #[fromstr(error_name = BadColor, error_vis = pub, error_derive(Clone, Hash, Debug))]
+    pub(crate) enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive_fromstr(error_name = "BadShade", error_vis = "pub(crate)")]
+    #[derive(Debug, PartialEq)]
+    pub(crate) enum Shade {
+        Light,
+        Dark,
+    }
+}
+
+// The enum's error is left out along with the enum, so this name is free.
+#[cfg(any())]
+#[derive(FromStr)]
+enum Gone {
+    Away,
+}
+#[allow(dead_code)]
+struct ParseGoneError;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(error_derive(Clone))]
+struct Port(u16);
+
+#[test]
+fn error_name_and_vis() {
+    let err: config::BadColor = "Blue".parse::<config::Color>().unwrap_err();
+    assert!(matches!(err, config::BadColor::UnknownVariant { .. }));
+    assert!(matches!("Grey".parse::<config::Shade>(), Err(config::BadShade::UnknownVariant { .. })));
+}
+
+#[test]
+fn error_derive_adds_traits() {
+    let err = "Blue".parse::<config::Color>().unwrap_err();
+    let errors: HashSet<_> = [err.clone(), err].into_iter().collect();
+    assert_eq!(errors.len(), 1);
+    let err = "x".parse::<Port>().unwrap_err();
+    assert_eq!(err.clone(), err);
+}
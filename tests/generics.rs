@@ -0,0 +1,77 @@
+use std::marker::PhantomData;
+
+use derive_fromstr::{FromStr, derive_fromstr};
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(display)]
+enum Mode<T> {
+    A,
+    B,
+    #[doc(hidden)]
+    _P(PhantomData<T>),
+}
+
+#[derive_fromstr(trim, lowercase, display)]
+#[derive(Debug, PartialEq)]
+enum Shape<T: Copy, U = u8>
+where
+    U: Clone,
+{
+    Dot,
+    Circle(T),
+    Rect { w: T, h: U },
+    #[fromstr(other)]
+    Other(String),
+}
+
+#[derive_fromstr(display, no_alloc)]
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Plus,
+    Minus,
+    #[fromstr(skip)]
+    Literal(&'a str),
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(display)]
+enum Wrap<'a, T> {
+    None,
+    Some(T),
+    #[fromstr(other)]
+    Raw(String),
+    #[fromstr(skip)]
+    Borrowed(&'a T),
+}
+
+#[test]
+fn type_parameters_only_bound_where_used() {
+    // `PhantomData<T>` is never parsed, so `T` needs no `FromStr`.
+    assert_eq!("A".parse::<Mode<Vec<u8>>>(), Ok(Mode::A));
+    assert!(matches!("_P".parse::<Mode<u8>>(), Err(ParseModeError::UnknownVariant { .. })));
+    assert_eq!(Mode::<Vec<u8>>::_P(PhantomData).to_string(), "_P");
+    assert_eq!(" circle(3) ".parse::<Shape<i32>>(), Ok(Shape::Circle(3)));
+    assert_eq!("Rect { w: 1.5, h: 2 }".parse::<Shape<f32, u16>>(), Ok(Shape::Rect { w: 1.5, h: 2 }));
+    assert!(matches!("circle(x)".parse::<Shape<i32>>(), Err(ParseShapeError::InvalidField { .. })));
+    assert_eq!("zzz".parse::<Shape<i32>>(), Ok(Shape::Other("zzz".into())));
+    assert_eq!(Shape::<u8, u8>::Rect { w: 1, h: 2 }.to_string(), "rect { w: 1, h: 2 }");
+}
+
+#[test]
+fn tables_of_generic_enums() {
+    assert_eq!(Mode::<u8>::VARIANTS, [Mode::A, Mode::B]);
+    assert_eq!(Mode::<std::rc::Rc<u8>>::iter().count(), 2);
+    assert_eq!(Mode::<u8>::COUNT, 2);
+    assert_eq!(Token::NAMES, ["Plus", "Minus"]);
+}
+
+#[test]
+fn lifetime_parameters() {
+    assert_eq!("Plus".parse::<Token<'_>>(), Ok(Token::Plus));
+    let literal = String::from("x");
+    assert_eq!(Token::Literal(&literal).to_string(), "Literal(x)");
+    assert_eq!("Some(4)".parse::<Wrap<'_, u8>>(), Ok(Wrap::Some(4)));
+    assert_eq!("q".parse::<Wrap<'_, u8>>(), Ok(Wrap::Raw("q".into())));
+    let n = 5;
+    assert_eq!(Wrap::Borrowed(&n).to_string(), "Borrowed(5)");
+}
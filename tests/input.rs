@@ -0,0 +1,60 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, lowercase)]
+enum Mode {
+    Fast,
+    Slow,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, lowercase, prefix)]
+enum Sub {
+    Stash,
+    Status,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, no_alloc)]
+enum Bare {
+    On,
+    Off,
+}
+
+#[test]
+fn errors_keep_the_input_as_given() {
+    let config = "mode =  Medium \n";
+    let value = &config[6..];
+    let Err(ParseModeError::UnknownVariant { input, normalized, span, .. }) = value.parse::<Mode>() else {
+        panic!("{:?} parsed", value);
+    };
+    assert_eq!(input, "  Medium \n");
+    assert_eq!(normalized, "medium");
+    assert_eq!(span, 2..8);
+    assert_eq!(&value[span], "Medium");
+    assert!(value.parse::<Mode>().unwrap_err().to_string().starts_with("Unknown variant \"  Medium \\n\""));
+}
+
+#[test]
+fn untrimmed_span_covers_everything() {
+    assert!(matches!("Medium".parse::<Mode>(), Err(ParseModeError::UnknownVariant { span, .. }) if span == (0..6)));
+}
+
+#[test]
+fn ambiguous_keeps_the_input_as_given() {
+    let Err(ParseSubError::Ambiguous { input, normalized, span, .. }) = " STa ".parse::<Sub>() else {
+        panic!("\" STa \" is not ambiguous");
+    };
+    assert_eq!(input, " STa ");
+    assert_eq!(normalized, "sta");
+    assert_eq!(span, 1..4);
+}
+
+#[test]
+fn without_alloc_only_the_span_is_kept() {
+    let value = "\tdim ";
+    let Err(ParseBareError::UnknownVariant { span, .. }) = value.parse::<Bare>() else {
+        panic!("{:?} parsed", value);
+    };
+    assert_eq!(&value[span], "dim");
+}
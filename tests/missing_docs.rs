@@ -0,0 +1,44 @@
+//! Everything the derive makes public is documented.
+#![deny(missing_docs)]
+
+use derive_fromstr::FromStr;
+
+/// Every kind of error, with `alloc`.
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, prefix, display)]
+pub enum Shape {
+    /// A point.
+    Dot,
+    /// A circle of some radius.
+    Circle(u8),
+}
+
+/// Every kind of error, without `alloc`.
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, prefix, display, no_alloc)]
+pub enum Bare {
+    /// A point.
+    Dot,
+    /// A circle of some radius.
+    Circle(u8),
+}
+
+/// A unit struct.
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim)]
+pub struct Auto;
+
+/// A unit struct without `alloc`.
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(no_alloc)]
+pub struct Manual;
+
+/// A newtype struct.
+#[derive(FromStr, Debug, PartialEq)]
+pub struct Port(pub u16);
+
+#[test]
+fn documented() {
+    assert_eq!(Shape::Dot.as_str(), "Dot");
+    assert_eq!("Dot".parse::<Bare>(), Ok(Bare::Dot));
+}
@@ -0,0 +1,79 @@
+use derive_fromstr::FromStr;
+
+// Errors without `alloc` keep where the input went wrong, not a copy of it,
+// so the patterns below name every field.
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, lowercase, no_alloc, display)]
+enum Mode {
+    Fast,
+    #[fromstr(alias = "crawl")]
+    Slow,
+    Rgb(u8, u8, u8),
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase, no_alloc)]
+enum Greek {
+    Ärger,
+    Σίγμα,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(no_alloc)]
+struct Marker;
+
+#[test]
+fn folds_case_in_place() {
+    assert_eq!(" FAST ".parse::<Mode>(), Ok(Mode::Fast));
+    assert_eq!("Crawl".parse::<Mode>(), Ok(Mode::Slow));
+    assert_eq!("RGB(1, 2, 3)".parse::<Mode>(), Ok(Mode::Rgb(1, 2, 3)));
+    assert_eq!("ÄRGER".parse::<Greek>(), Ok(Greek::Ärger));
+    assert_eq!("ΣΊΓΜΑ".parse::<Greek>(), Ok(Greek::Σίγμα));
+}
+
+#[test]
+fn input_too_long_to_fold_is_unknown() {
+    // Within the length limit, but too many bytes for the folding buffer.
+    assert_eq!(
+        "ΣΊΓΜΑΣΣ".parse::<Greek>(),
+        Err(ParseGreekError::UnknownVariant { span: 0..14, expected: &["ärger", "σίγμα"] })
+    );
+    assert_eq!("ÄRGERLICH".parse::<Greek>(), Err(ParseGreekError::TooLong { len: 9, max: 5 }));
+}
+
+#[test]
+fn errors_hold_spans() {
+    assert_eq!(" ".parse::<Mode>(), Err(ParseModeError::Empty));
+    assert_eq!(
+        " medium ".parse::<Mode>(),
+        Err(ParseModeError::UnknownVariant { span: 1..7, expected: &["fast", "slow", "rgb(..)"] })
+    );
+    assert_eq!("rgb(1, 2)".parse::<Mode>(), Err(ParseModeError::MalformedVariant { variant: "rgb", span: 0..9 }));
+    assert_eq!("rgb(1, 2, x)".parse::<Mode>(), Err(ParseModeError::InvalidField { variant: "rgb", field: "2" }));
+    assert_eq!(" Other".parse::<Marker>(), Err(ParseMarkerError::Mismatch { span: 0..6, expected: "Marker" }));
+}
+
+#[test]
+fn display_needs_no_alloc_either() {
+    let mut buf = [0u8; 32];
+    let mut w = Writer { buf: &mut buf, len: 0 };
+    core::fmt::write(&mut w, format_args!("{}|{}", Mode::Slow, Mode::Rgb(1, 2, 3))).unwrap();
+    let len = w.len;
+    assert_eq!(&buf[..len], b"slow|rgb(1, 2, 3)");
+    assert_eq!(" medium ".parse::<Mode>().unwrap_err().to_string(), "Unknown variant at 1..7 (expected one of: fast, slow, rgb(..))");
+}
+
+struct Writer<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl core::fmt::Write for Writer<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        self.buf.get_mut(self.len..end).ok_or(core::fmt::Error)?.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+// The generated code names everything by its full path, so it compiles
+// where nothing is in scope by default.
+mod types {
+    #![no_implicit_prelude]
+
+    use ::derive_fromstr::{FromStr, derive_fromstr};
+
+    #[derive(FromStr, Debug, PartialEq)]
+    #[fromstr(trim, lowercase, display, prefix, discriminant(hex), max_expected = 1)]
+    pub enum Sub {
+        Status,
+        Stop,
+        Add = 7,
+    }
+
+    #[derive(FromStr, Debug, PartialEq)]
+    #[fromstr(lowercase, display, truncate(2, graphemes))]
+    pub enum Shape {
+        Dot,
+        Circle(::core::primitive::u8),
+        Rect { w: ::core::primitive::u8, h: ::core::primitive::u8 },
+        #[allow(dead_code)]
+        #[fromstr(skip)]
+        Hidden,
+    }
+
+    #[derive_fromstr(trim, lowercase, display, prefix, index, no_alloc)]
+    #[derive(Debug, PartialEq)]
+    pub enum Small {
+        Alpha,
+        Alps,
+        Beta(::core::primitive::u8),
+        #[fromstr(other)]
+        Gamma,
+    }
+
+    #[derive(FromStr, Debug, PartialEq)]
+    #[fromstr(display, index)]
+    pub enum Wrapper<T> {
+        Nothing,
+        Just(T),
+        #[fromstr(other)]
+        Raw(::std::string::String),
+    }
+
+    #[derive(FromStr, Debug, PartialEq)]
+    #[fromstr(display, trim, lowercase)]
+    pub struct Port(pub ::core::primitive::u16);
+
+    #[derive(FromStr, Debug, PartialEq)]
+    #[fromstr(display, trim)]
+    pub struct Limit<T>(pub T);
+
+    #[derive(FromStr, Debug, PartialEq)]
+    #[fromstr(display, trim, lowercase, no_alloc)]
+    pub struct Unit;
+
+    #[derive(Debug, PartialEq)]
+    pub struct ConfigError(pub &'static ::core::primitive::str);
+
+    impl ConfigError {
+        fn parse_error(name: &'static ::core::primitive::str, _input: &::core::primitive::str) -> Self {
+            ConfigError(name)
+        }
+    }
+
+    #[derive(FromStr, Debug, PartialEq)]
+    #[fromstr(error = ConfigError, lowercase, prefix)]
+    pub enum Switch {
+        On,
+        Off(::core::primitive::u8),
+    }
+}
+
+use types::*;
+
+#[test]
+fn parses_without_a_prelude() {
+    assert_eq!(" ST ".parse::<Sub>().unwrap_err().to_string(), "Ambiguous variant \" ST \" (could be status, stop)");
+    assert_eq!("stat".parse::<Sub>(), Ok(Sub::Status));
+    assert_eq!("0x7".parse::<Sub>(), Ok(Sub::Add));
+    assert_eq!("xyz".parse::<Sub>().unwrap_err().to_string(), "Unknown variant \"xyz\" (expected one of: status and 2 more)");
+    assert_eq!("Do".parse::<Shape>(), Ok(Shape::Dot));
+    assert_eq!("Dod".parse::<Shape>(), Err(ParseShapeError::UnknownVariant {
+        input: "Dod".into(),
+        normalized: "dod".into(),
+        span: 0..3,
+        expected: &["dot", "circle(..)", "rect { .. }"],
+        suggestions: vec!["dot"],
+    }));
+    assert_eq!("circle(3)".parse::<Shape>(), Ok(Shape::Circle(3)));
+    assert_eq!("Rect { w: 1, h: 2 }".parse::<Shape>(), Ok(Shape::Rect { w: 1, h: 2 }));
+    assert_eq!(" al ".parse::<Small>(), Err(ParseSmallError::Ambiguous { span: 1..3, candidates: ["alpha", "alps"] }));
+    assert_eq!("1".parse::<Small>(), Ok(Small::Alps));
+    assert_eq!("beta(2)".parse::<Small>(), Ok(Small::Beta(2)));
+    assert_eq!("delta".parse::<Small>(), Ok(Small::Gamma));
+    assert_eq!("Just(5)".parse::<Wrapper<u8>>(), Ok(Wrapper::Just(5)));
+    assert_eq!("0".parse::<Wrapper<u8>>(), Ok(Wrapper::Nothing));
+    assert_eq!("Maybe".parse::<Wrapper<u8>>(), Ok(Wrapper::Raw("Maybe".into())));
+    assert_eq!(" 80 ".parse::<Port>(), Ok(Port(80)));
+    assert_eq!(" 3 ".parse::<Limit<u8>>(), Ok(Limit(3)));
+    assert_eq!(" UNIT ".parse::<Unit>(), Ok(Unit));
+    assert_eq!("o".parse::<Switch>(), Ok(Switch::On));
+    assert_eq!("off(x)".parse::<Switch>(), Err(ConfigError("Switch")));
+}
+
+#[test]
+fn displays_without_a_prelude() {
+    assert_eq!(Sub::Add.to_string(), "add");
+    assert_eq!(Shape::Rect { w: 1, h: 2 }.to_string(), "rect { w: 1, h: 2 }");
+    assert_eq!(Small::Beta(2).to_string(), "beta(2)");
+    assert_eq!(Wrapper::Just(5).to_string(), "Just(5)");
+    assert_eq!(Port(80).to_string(), "80");
+    assert_eq!(format!("{:>5}", Unit), " unit");
+    assert_eq!(Sub::VARIANTS, [Sub::Status, Sub::Stop, Sub::Add]);
+    assert_eq!(Sub::iter().count(), Sub::COUNT);
+}
@@ -0,0 +1,58 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(discriminant)]
+enum Level {
+    Debug = 1,
+    Info,
+    Warn = 10,
+    Error = -1,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(discriminant(hex))]
+enum Code {
+    Ok = 0x20,
+    Gone = 0x41,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(index)]
+enum Ordinal {
+    First,
+    Second,
+    Third,
+}
+
+#[test]
+fn explicit_and_implicit_discriminants() {
+    assert_eq!("1".parse::<Level>(), Ok(Level::Debug));
+    assert_eq!("2".parse::<Level>(), Ok(Level::Info));
+    assert_eq!("10".parse::<Level>(), Ok(Level::Warn));
+    assert_eq!("-1".parse::<Level>(), Ok(Level::Error));
+    assert_eq!("Warn".parse::<Level>(), Ok(Level::Warn));
+    assert!("3".parse::<Level>().is_err());
+    assert!("0x0a".parse::<Level>().is_err());
+    // Only a `-` sign, as in the source.
+    assert!("+2".parse::<Level>().is_err());
+    assert!("-".parse::<Level>().is_err());
+    assert!("- 1".parse::<Level>().is_err());
+}
+
+#[test]
+fn hex_discriminants() {
+    assert_eq!("0x20".parse::<Code>(), Ok(Code::Ok));
+    assert_eq!("0X41".parse::<Code>(), Ok(Code::Gone));
+    assert_eq!("65".parse::<Code>(), Ok(Code::Gone));
+    assert!("0x".parse::<Code>().is_err());
+    assert!("0x+41".parse::<Code>().is_err());
+    assert!("+65".parse::<Code>().is_err());
+}
+
+#[test]
+fn declaration_indices() {
+    assert_eq!("0".parse::<Ordinal>(), Ok(Ordinal::First));
+    assert_eq!("2".parse::<Ordinal>(), Ok(Ordinal::Third));
+    assert!("3".parse::<Ordinal>().is_err());
+    assert!("+1".parse::<Ordinal>().is_err());
+}
@@ -0,0 +1,45 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, lowercase, display)]
+enum Proto {
+    Http,
+    Https,
+    #[fromstr(other)]
+    Other(String),
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+enum Wire {
+    Ping,
+    #[fromstr(other)]
+    Unknown,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+enum Boxed {
+    A,
+    #[fromstr(other)]
+    Other(Box<str>),
+}
+
+#[test]
+fn other_captures_unmatched_input() {
+    assert_eq!(" HTTP ".parse::<Proto>(), Ok(Proto::Http));
+    // The capture is trimmed but keeps its case.
+    assert_eq!(" Gopher ".parse::<Proto>(), Ok(Proto::Other("Gopher".into())));
+    assert_eq!("".parse::<Proto>(), Ok(Proto::Other(String::new())));
+    assert_eq!(Proto::Other("Gopher".into()).to_string(), "Gopher");
+}
+
+#[test]
+fn unit_other_keeps_its_own_spelling() {
+    assert_eq!("Ping".parse::<Wire>(), Ok(Wire::Ping));
+    assert_eq!("Unknown".parse::<Wire>(), Ok(Wire::Unknown));
+    assert_eq!("pong".parse::<Wire>(), Ok(Wire::Unknown));
+}
+
+#[test]
+fn other_holding_box_str() {
+    assert_eq!("b".parse::<Boxed>(), Ok(Boxed::Other("b".into())));
+}
@@ -0,0 +1,80 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase, prefix)]
+enum Sub {
+    Commit,
+    Checkout,
+    Clone,
+    Status,
+    Stash,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase, prefix)]
+enum Edit {
+    #[fromstr(prefix(min = 3))]
+    Remove,
+    Add,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase)]
+enum Cmd {
+    #[fromstr(prefix)]
+    Status,
+    Stop,
+    Add,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(prefix, no_alloc)]
+enum Small {
+    Alpha,
+    Alps,
+    Alto,
+    Beta,
+}
+
+#[test]
+fn unique_prefixes_parse() {
+    assert_eq!("comm".parse::<Sub>(), Ok(Sub::Commit));
+    assert_eq!("CL".parse::<Sub>(), Ok(Sub::Clone));
+    assert_eq!("stat".parse::<Sub>(), Ok(Sub::Status));
+    assert_eq!("stas".parse::<Sub>(), Ok(Sub::Stash));
+    assert_eq!("checkout".parse::<Sub>(), Ok(Sub::Checkout));
+}
+
+#[test]
+fn per_variant_minimum_length() {
+    assert!(matches!("re".parse::<Edit>(), Err(ParseEditError::UnknownVariant { .. })));
+    assert_eq!("rem".parse::<Edit>(), Ok(Edit::Remove));
+    assert_eq!("a".parse::<Edit>(), Ok(Edit::Add));
+}
+
+#[test]
+fn ambiguous_prefixes_list_the_candidates() {
+    let err = "C".parse::<Sub>().unwrap_err();
+    assert_eq!(
+        err,
+        ParseSubError::Ambiguous { input: "C".into(), normalized: "c".into(), span: 0..1, candidates: vec!["commit", "checkout", "clone"] }
+    );
+    assert_eq!(err.to_string(), "Ambiguous variant \"C\" (could be commit, checkout, clone)");
+}
+
+#[test]
+fn variants_without_prefix_still_make_abbreviations_ambiguous() {
+    assert_eq!("stat".parse::<Cmd>(), Ok(Cmd::Status));
+    assert!(matches!("st".parse::<Cmd>(), Err(ParseCmdError::Ambiguous { candidates, .. }) if candidates == ["status", "stop"]));
+    assert_eq!("stop".parse::<Cmd>(), Ok(Cmd::Stop));
+    assert!(matches!("sto".parse::<Cmd>(), Err(ParseCmdError::UnknownVariant { .. })));
+    assert!(matches!("ad".parse::<Cmd>(), Err(ParseCmdError::UnknownVariant { .. })));
+}
+
+#[test]
+fn no_alloc_keeps_the_first_two_candidates() {
+    assert_eq!("B".parse::<Small>(), Ok(Small::Beta));
+    assert_eq!("Al".parse::<Small>(), Err(ParseSmallError::Ambiguous { span: 0..2, candidates: ["Alpha", "Alps"] }));
+    assert_eq!("Alp".parse::<Small>(), Err(ParseSmallError::Ambiguous { span: 0..3, candidates: ["Alpha", "Alps"] }));
+    assert_eq!("Alt".parse::<Small>(), Ok(Small::Alto));
+}
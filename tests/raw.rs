@@ -0,0 +1,37 @@
+#![allow(non_camel_case_types)]
+
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(display)]
+enum Keyword {
+    r#fn,
+    r#type,
+    r#match,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase, truncate(2), rename_all = "UPPERCASE", display)]
+enum r#Loop {
+    r#Loop,
+    r#While,
+}
+
+#[test]
+fn raw_variants_parse_without_the_prefix() {
+    assert_eq!("fn".parse::<Keyword>(), Ok(Keyword::r#fn));
+    assert_eq!("type".parse::<Keyword>(), Ok(Keyword::r#type));
+    assert!("r#type".parse::<Keyword>().is_err());
+    assert_eq!(Keyword::NAMES, ["fn", "type", "match"]);
+    assert_eq!(Keyword::r#match.to_string(), "match");
+}
+
+#[test]
+fn raw_names_under_rename_all_and_truncate() {
+    assert_eq!("loop".parse::<Loop>(), Ok(Loop::Loop));
+    assert_eq!("Wh".parse::<Loop>(), Ok(Loop::While));
+    assert_eq!(Loop::While.to_string(), "while");
+    // The error type is named after the bare name too.
+    let err: ParseLoopError = "for".parse::<Loop>().unwrap_err();
+    assert_eq!(err.to_string(), "Unknown variant \"for\" (expected one of: loop, while)");
+}
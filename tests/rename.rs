@@ -0,0 +1,47 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+enum Arch {
+    #[fromstr(rename = "x86-64")]
+    X86_64,
+    Arm,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, lowercase)]
+enum Folded {
+    #[fromstr(rename = "X86-64", alias = "AMD64")]
+    X86_64,
+    Arm,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(truncate(3))]
+enum Truncated {
+    #[fromstr(rename = "Aarch64")]
+    Arm,
+    Riscv,
+}
+
+#[test]
+fn rename_replaces_the_variant_name() {
+    assert_eq!("x86-64".parse::<Arch>(), Ok(Arch::X86_64));
+    assert!("X86_64".parse::<Arch>().is_err());
+    assert_eq!("Arm".parse::<Arch>(), Ok(Arch::Arm));
+}
+
+#[test]
+fn rename_goes_through_trim_and_lowercase() {
+    assert_eq!(" X86-64 ".parse::<Folded>(), Ok(Folded::X86_64));
+    assert_eq!("x86-64".parse::<Folded>(), Ok(Folded::X86_64));
+    assert_eq!("amd64".parse::<Folded>(), Ok(Folded::X86_64));
+    assert_eq!(Folded::NAMES, ["x86-64", "arm"]);
+    assert_eq!("ARM".parse::<Folded>(), Ok(Folded::Arm));
+}
+
+#[test]
+fn rename_is_what_gets_truncated() {
+    assert_eq!("Aar".parse::<Truncated>(), Ok(Truncated::Arm));
+    assert!("Arm".parse::<Truncated>().is_err());
+    assert_eq!("Ris".parse::<Truncated>(), Ok(Truncated::Riscv));
+}
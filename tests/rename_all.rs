@@ -0,0 +1,49 @@
+use derive_fromstr::FromStr;
+
+macro_rules! convention {
+    ($name:ident, $rule:literal, [$dark:literal, $http:literal, $ipv4:literal, $x86:literal]) => {
+        #[derive(FromStr, Debug, PartialEq)]
+        #[fromstr(rename_all = $rule)]
+        enum $name {
+            DarkBlue,
+            HTTPServer,
+            Ipv4Addr,
+            X86_64,
+        }
+
+        assert_eq!($dark.parse::<$name>(), Ok($name::DarkBlue));
+        assert_eq!($http.parse::<$name>(), Ok($name::HTTPServer));
+        assert_eq!($ipv4.parse::<$name>(), Ok($name::Ipv4Addr));
+        assert_eq!($x86.parse::<$name>(), Ok($name::X86_64));
+    };
+}
+
+#[test]
+fn conventions() {
+    convention!(Lower, "lowercase", ["darkblue", "httpserver", "ipv4addr", "x86_64"]);
+    convention!(Upper, "UPPERCASE", ["DARKBLUE", "HTTPSERVER", "IPV4ADDR", "X86_64"]);
+    convention!(Camel, "camelCase", ["darkBlue", "httpServer", "ipv4Addr", "x8664"]);
+    convention!(Pascal, "PascalCase", ["DarkBlue", "HttpServer", "Ipv4Addr", "X8664"]);
+    convention!(Snake, "snake_case", ["dark_blue", "http_server", "ipv4_addr", "x86_64"]);
+    convention!(ScreamingSnake, "SCREAMING_SNAKE_CASE", ["DARK_BLUE", "HTTP_SERVER", "IPV4_ADDR", "X86_64"]);
+    convention!(Kebab, "kebab-case", ["dark-blue", "http-server", "ipv4-addr", "x86-64"]);
+    convention!(ScreamingKebab, "SCREAMING-KEBAB-CASE", ["DARK-BLUE", "HTTP-SERVER", "IPV4-ADDR", "X86-64"]);
+    convention!(Title, "Title Case", ["Dark Blue", "Http Server", "Ipv4 Addr", "X86 64"]);
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(rename_all = "kebab-case")]
+enum Mixed {
+    X86_64,
+    V2Beta,
+    #[fromstr(rename = "custom")]
+    Renamed,
+}
+
+#[test]
+fn underscores_digits_and_explicit_renames() {
+    assert_eq!("x86-64".parse::<Mixed>(), Ok(Mixed::X86_64));
+    assert_eq!("v2-beta".parse::<Mixed>(), Ok(Mixed::V2Beta));
+    assert_eq!("custom".parse::<Mixed>(), Ok(Mixed::Renamed));
+    assert!("renamed".parse::<Mixed>().is_err());
+}
@@ -0,0 +1,44 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase, truncate(4), display, index)]
+enum State {
+    #[fromstr(skip)]
+    Uninitialized,
+    Running,
+    Stopped,
+    #[fromstr(skip)]
+    Failed(u8),
+    #[doc(hidden)]
+    #[fromstr(skip)]
+    __NonExhaustive,
+}
+
+#[test]
+fn skipped_variants_never_parse() {
+    for input in ["uninitialized", "unin", "failed(1)", "__nonexhaustive", "0"] {
+        assert!(input.parse::<State>().is_err(), "{} parsed", input);
+    }
+    assert_eq!("runn".parse::<State>(), Ok(State::Running));
+    // Indices are still counted in declaration order.
+    assert_eq!("1".parse::<State>(), Ok(State::Running));
+    assert_eq!("2".parse::<State>(), Ok(State::Stopped));
+}
+
+#[test]
+fn skipped_variants_are_not_listed_or_suggested() {
+    assert_eq!(State::NAMES, ["running", "stopped"]);
+    assert_eq!(State::VARIANTS, [State::Running, State::Stopped]);
+    let Err(ParseStateError::UnknownVariant { expected, suggestions, .. }) = "failed".parse::<State>() else {
+        panic!("\"failed\" parsed");
+    };
+    assert_eq!(expected, ["running", "stopped"]);
+    assert!(suggestions.is_empty());
+}
+
+#[test]
+fn skipped_variants_still_display() {
+    assert_eq!(State::Uninitialized.to_string(), "uninitialized");
+    assert_eq!(State::Failed(3).to_string(), "failed(3)");
+    assert_eq!(State::__NonExhaustive.to_string(), "__nonexhaustive");
+}
@@ -0,0 +1,113 @@
+use std::error::Error;
+use std::str::FromStr;
+
+use derive_fromstr::{FromStr, derive_fromstr};
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, lowercase, display, rename_all = "snake_case")]
+struct AutoMode;
+
+#[derive_fromstr(no_alloc)]
+#[derive(Debug, PartialEq)]
+struct Nothing;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim, display)]
+struct Port(u16);
+
+#[derive(FromStr, Debug, PartialEq, Clone, Copy)]
+#[fromstr(lowercase)]
+enum LogLevel {
+    Info,
+    Warn,
+}
+
+#[derive_fromstr(trim, lowercase)]
+#[derive(Debug, PartialEq)]
+struct Level(LogLevel);
+
+struct WrapperError;
+
+impl WrapperError {
+    fn parse_error(_: &'static str, _: &str) -> Self {
+        WrapperError
+    }
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(error = WrapperError, display)]
+struct Wrapper<T>(T);
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(trim)]
+struct Threshold<T>(T);
+
+// A field whose error is neither comparable nor `'static`.
+#[derive(Debug)]
+struct Script(String);
+
+impl FromStr for Script {
+    type Err = Box<dyn Error + Send + Sync>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('#') {
+            Some(body) => Ok(Script(body.to_string())),
+            None => Err("scripts start with `#`".into()),
+        }
+    }
+}
+
+#[derive(FromStr, Debug)]
+struct Hook(Script);
+
+#[test]
+fn unit_structs_parse_from_their_name() {
+    assert_eq!(" AUTO_mode ".parse::<AutoMode>(), Ok(AutoMode));
+    assert_eq!(AutoMode.to_string(), "auto_mode");
+    assert_eq!("x".parse::<AutoMode>(), Err(ParseAutoModeError::Mismatch { input: "x".into(), span: 0..1, expected: "auto_mode" }));
+    assert_eq!("  ".parse::<AutoMode>(), Err(ParseAutoModeError::Empty));
+    assert_eq!("Nothing".parse::<Nothing>(), Ok(Nothing));
+    assert_eq!("nothing".parse::<Nothing>(), Err(ParseNothingError::Mismatch { span: 0..7, expected: "Nothing" }));
+}
+
+#[test]
+fn newtypes_parse_through_their_field() {
+    assert_eq!(" 8080 ".parse::<Port>(), Ok(Port(8080)));
+    assert_eq!(Port(1).to_string(), "1");
+    assert_eq!(" WARN".parse::<Level>(), Ok(Level(LogLevel::Warn)));
+    assert!(matches!("5".parse::<Wrapper<u8>>(), Ok(Wrapper(5))));
+    assert!(matches!("x".parse::<Wrapper<u8>>(), Err(WrapperError)));
+    assert_eq!(Wrapper(2.5).to_string(), "2.5");
+}
+
+#[test]
+fn newtype_errors_wrap_the_field_error() {
+    let err = "x".parse::<Port>().unwrap_err();
+    assert_eq!(err, ParsePortError::Invalid("x".parse::<u16>().unwrap_err()));
+    assert_eq!(err.to_string(), "Invalid Port: invalid digit found in string");
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), "invalid digit found in string");
+    assert!(source.is::<std::num::ParseIntError>());
+    let err = "x".parse::<Level>().unwrap_err();
+    assert!(matches!(&err, ParseLevelError::Invalid(ParseLogLevelError::UnknownVariant { .. })));
+    assert!(err.source().unwrap().is::<ParseLogLevelError>());
+}
+
+#[test]
+fn generic_newtypes_get_a_generic_error() {
+    assert_eq!(" 7 ".parse::<Threshold<u16>>(), Ok(Threshold(7)));
+    assert_eq!(" 0.5".parse::<Threshold<f32>>(), Ok(Threshold(0.5)));
+    let err = "-1".parse::<Threshold<u16>>().unwrap_err();
+    assert_eq!(err, ParseThresholdError::Invalid("-1".parse::<u16>().unwrap_err()));
+    assert_eq!(err.to_string(), "Invalid Threshold: invalid digit found in string");
+    assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+    let ParseThresholdError::Invalid(err) = "x".parse::<Threshold<Script>>().unwrap_err();
+    assert_eq!(err.to_string(), "scripts start with `#`");
+}
+
+#[test]
+fn newtype_errors_need_not_be_comparable() {
+    assert_eq!("#run".parse::<Hook>().unwrap().0.0, "run");
+    let ParseHookError::Invalid(err) = "run".parse::<Hook>().unwrap_err();
+    assert_eq!(err.to_string(), "scripts start with `#`");
+}
@@ -0,0 +1,61 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase)]
+enum Color {
+    Red,
+    Green,
+    Grey,
+    Blue,
+    #[fromstr(alias = "violet")]
+    Purple,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(max_distance = 0)]
+enum Quiet {
+    Green,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(max_distance = 4)]
+enum Loose {
+    Orange,
+}
+
+fn suggestions<T: std::str::FromStr<Err = ParseColorError>>(input: &str) -> Vec<&'static str> {
+    match input.parse::<T>() {
+        Err(ParseColorError::UnknownVariant { suggestions, .. }) => suggestions,
+        _ => panic!("{:?} parsed", input),
+    }
+}
+
+#[test]
+fn closest_spellings_first() {
+    assert_eq!(suggestions::<Color>("blu"), ["blue"]);
+    assert_eq!(suggestions::<Color>("grean"), ["green", "grey"]);
+    assert_eq!(suggestions::<Color>("gren"), ["green", "grey", "red"]);
+    assert_eq!(suggestions::<Color>("violett"), ["violet"]);
+    assert!(suggestions::<Color>("yellow").is_empty());
+}
+
+#[test]
+fn short_inputs_get_no_suggestions() {
+    // Two edits turn "xd" into "red", but that replaces every char of it.
+    assert!(suggestions::<Color>("xd").is_empty());
+    assert_eq!(suggestions::<Color>("rd"), ["red"]);
+}
+
+#[test]
+fn display_asks_did_you_mean() {
+    let err = "blu".parse::<Color>().unwrap_err();
+    assert_eq!(err.to_string(), "Unknown variant \"blu\", did you mean \"blue\"? (expected one of: red, green, grey, blue, purple)");
+    let err = "gren".parse::<Color>().unwrap_err();
+    assert!(err.to_string().starts_with("Unknown variant \"gren\", did you mean \"green\", \"grey\" or \"red\"?"));
+}
+
+#[test]
+fn max_distance_sets_how_far_to_look() {
+    assert!(matches!("Gren".parse::<Quiet>(), Err(ParseQuietError::UnknownVariant { suggestions, .. }) if suggestions.is_empty()));
+    assert!(matches!("Orxxxx".parse::<Loose>(), Err(ParseLooseError::UnknownVariant { suggestions, .. }) if suggestions == ["Orange"]));
+}
@@ -0,0 +1,36 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq, Clone, Copy)]
+#[fromstr(rename_all = "snake_case")]
+enum Color {
+    Red,
+    DarkGreen,
+    #[fromstr(rename = "navy")]
+    Blue,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+enum Never {}
+
+#[test]
+fn tables_list_variants_in_declaration_order() {
+    assert_eq!(Color::VARIANTS, &[Color::Red, Color::DarkGreen, Color::Blue]);
+    assert_eq!(Color::NAMES, &["red", "dark_green", "navy"]);
+    assert_eq!(Color::COUNT, 3);
+    assert_eq!(Color::iter().collect::<Vec<_>>(), Color::VARIANTS);
+}
+
+#[test]
+fn names_parse_to_the_matching_variant() {
+    for (variant, name) in Color::iter().zip(Color::NAMES) {
+        assert_eq!(name.parse::<Color>(), Ok(variant));
+    }
+}
+
+#[test]
+fn empty_enum_has_empty_tables() {
+    assert!(Never::VARIANTS.is_empty());
+    assert!(Never::NAMES.is_empty());
+    assert_eq!(Never::COUNT, 0);
+    assert_eq!(Never::iter().count(), 0);
+}
@@ -0,0 +1,47 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(truncate(3))]
+enum Size {
+    Größe,
+    Maß,
+    Gewicht,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase, truncate(4, graphemes))]
+enum Drink {
+    #[fromstr(rename = "cafe\u{301}-au-lait")]
+    Coffee,
+    Tea,
+}
+
+#[derive(FromStr, Debug, PartialEq)]
+#[fromstr(lowercase, truncate(2, chars))]
+enum Stage {
+    Über,
+    Unter,
+}
+
+#[test]
+fn truncates_by_chars() {
+    assert_eq!("Grö".parse::<Size>(), Ok(Size::Größe));
+    assert_eq!("Größe".parse::<Size>(), Ok(Size::Größe));
+    // Names no longer than the cut have no truncated form.
+    assert_eq!("Maß".parse::<Size>(), Ok(Size::Maß));
+    assert_eq!("Gew".parse::<Size>(), Ok(Size::Gewicht));
+    assert!("Gr".parse::<Size>().is_err());
+}
+
+#[test]
+fn truncates_by_graphemes() {
+    assert_eq!("cafe\u{301}".parse::<Drink>(), Ok(Drink::Coffee));
+    assert!("cafe".parse::<Drink>().is_err());
+    assert_eq!("tea".parse::<Drink>(), Ok(Drink::Tea));
+}
+
+#[test]
+fn folds_case_after_the_cut() {
+    assert_eq!("ÜB".parse::<Stage>(), Ok(Stage::Über));
+    assert_eq!("un".parse::<Stage>(), Ok(Stage::Unter));
+}
@@ -0,0 +1,7 @@
+// Diagnostics for invalid input, checked against the `.stderr` file next to
+// each case. Run with `TRYBUILD=overwrite` to accept new output.
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,26 @@
+use derive_fromstr::derive_fromstr;
+
+#[derive_fromstr(index)]
+enum Level {
+    Low,
+    #[cfg(any())]
+    Medium,
+    High,
+}
+
+#[derive_fromstr(discriminant)]
+enum Code {
+    #[cfg(any())]
+    Ok = 1,
+    Gone,
+}
+
+#[derive_fromstr]
+enum Value {
+    Known,
+    #[cfg(all())]
+    #[fromstr(other)]
+    Unknown,
+}
+
+fn main() {}
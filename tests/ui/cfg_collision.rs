@@ -0,0 +1,19 @@
+use derive_fromstr::derive_fromstr;
+
+// Both variants are compiled in, so "native" is ambiguous after all. `Mobile`
+// never is, so its alias is free.
+#[derive_fromstr]
+enum Platform {
+    #[cfg(not(any()))]
+    #[fromstr(rename = "native")]
+    Unix,
+    #[cfg_attr(not(any()), cfg(not(any())))]
+    #[fromstr(rename = "native")]
+    Windows,
+    Web,
+    #[cfg(any())]
+    #[fromstr(alias = "web")]
+    Mobile,
+}
+
+fn main() {}
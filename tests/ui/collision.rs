@@ -0,0 +1,18 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+#[fromstr(truncate(3))]
+enum Truncated {
+    Green,
+    Grey,
+}
+
+#[derive(FromStr)]
+#[fromstr(lowercase)]
+enum Folded {
+    Red,
+    #[fromstr(alias = "red")]
+    Crimson,
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+use derive_fromstr::FromStr;
+
+pub struct OwnedError;
+
+impl OwnedError {
+    fn parse_error(_name: String, _input: &str) -> Self {
+        OwnedError
+    }
+}
+
+pub struct NoConstructor;
+
+#[derive(FromStr)]
+#[fromstr(error = OwnedError)]
+enum Mode {
+    Fast,
+    Slow,
+}
+
+#[derive(FromStr)]
+#[fromstr(error = NoConstructor)]
+struct Port(u16);
+
+#[derive(FromStr)]
+#[fromstr(error = OwnedError, error_name = ModeError)]
+enum Level {
+    Low,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+mod config {
+    use derive_fromstr::FromStr;
+
+    #[derive(FromStr)]
+    #[fromstr(error_vis = "pub(nowhere)", error_derive = Clone)]
+    pub enum Level {
+        Low,
+    }
+
+    #[derive(FromStr)]
+    #[fromstr(error_name = "not a name")]
+    pub enum Mode {
+        Fast,
+    }
+}
+
+fn main() {}
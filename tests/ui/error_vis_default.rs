@@ -0,0 +1,15 @@
+mod config {
+    use derive_fromstr::FromStr;
+
+    #[derive(FromStr)]
+    struct Hidden;
+}
+
+// The error is as private as the type by default.
+fn hidden() -> Option<config::ParseHiddenError> {
+    None
+}
+
+fn main() {
+    hidden();
+}
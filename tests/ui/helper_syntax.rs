@@ -0,0 +1,9 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+#[fromstr = "lowercase"]
+enum Color {
+    Red,
+}
+
+fn main() {}
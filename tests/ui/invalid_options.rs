@@ -0,0 +1,27 @@
+use derive_fromstr::{FromStr, derive_fromstr};
+
+#[derive(FromStr)]
+#[fromstr(lowercse, truncate("3"), trim, trim, display = "yes")]
+enum Color {
+    #[fromstr(rename = 3, alias("x"), colour)]
+    Red,
+    #[fromstr(prefix(min = 2), prefix)]
+    Green,
+}
+
+#[derive_fromstr(truncate(99999999999999999999), truncate(0), "lowercase")]
+enum Size {
+    Small,
+}
+
+#[derive_fromstr(truncate(0))]
+enum Zero {
+    A,
+}
+
+#[derive_fromstr(max_distance = "2", error = "Type", error_vis = crate::Error)]
+enum Unit {
+    A,
+}
+
+fn main() {}
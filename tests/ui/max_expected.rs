@@ -0,0 +1,10 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+#[fromstr(max_expected = 0)]
+enum Level {
+    Info,
+    Warn,
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+#[fromstr(discriminant, index)]
+enum Shared {
+    A = 1,
+    B = 0,
+}
+
+#[derive(FromStr)]
+#[fromstr(index)]
+enum Named {
+    A,
+    #[fromstr(rename = "0")]
+    B,
+}
+
+const BASE: isize = 4;
+
+#[derive(FromStr)]
+#[fromstr(discriminant)]
+enum Computed {
+    A = BASE,
+}
+
+#[derive(FromStr)]
+#[fromstr(discriminant(oct))]
+enum Octal {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+enum Two {
+    #[fromstr(other)]
+    A,
+    #[fromstr(other)]
+    B,
+}
+
+#[derive(FromStr)]
+enum Fields {
+    #[fromstr(other)]
+    Pair(String, String),
+}
+
+#[derive(FromStr)]
+enum Renamed {
+    #[fromstr(other, rename = "x")]
+    Other(String),
+}
+
+fn main() {}
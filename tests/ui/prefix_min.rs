@@ -0,0 +1,11 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+#[fromstr(prefix(min = 0))]
+enum Sub {
+    Commit,
+    #[fromstr(prefix(max = 2))]
+    Clone,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#![allow(non_camel_case_types)]
+
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+enum Keyword {
+    r#type,
+    #[fromstr(rename = "type")]
+    Kind,
+}
+
+fn main() {}
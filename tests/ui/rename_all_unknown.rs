@@ -0,0 +1,9 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+#[fromstr(rename_all = "snake-case")]
+enum Color {
+    DarkBlue,
+}
+
+fn main() {}
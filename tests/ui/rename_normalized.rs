@@ -0,0 +1,11 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+#[fromstr(trim, lowercase)]
+enum Arch {
+    #[fromstr(rename = " x86 ")]
+    X86,
+    Arm,
+}
+
+fn main() {}
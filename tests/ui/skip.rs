@@ -0,0 +1,14 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+enum State {
+    Running,
+    #[fromstr(skip, alias = "init")]
+    Uninitialized,
+    #[fromstr(skip, other)]
+    Unknown,
+    #[fromstr(skip = true)]
+    Stopped,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+struct Point {
+    x: u8,
+    y: u8,
+}
+
+#[derive(FromStr)]
+#[fromstr(prefix, truncate(2))]
+struct Port(u16);
+
+#[derive(FromStr)]
+#[fromstr(lowercase, no_alloc)]
+struct Name(&'static str);
+
+#[derive(FromStr)]
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use derive_fromstr::FromStr;
+
+#[derive(FromStr)]
+#[fromstr(truncate(4))]
+enum Drink {
+    #[fromstr(rename = "Cafe\u{301}")]
+    Coffee,
+    Tea,
+}
+
+#[derive(FromStr)]
+#[fromstr(truncate(2, words))]
+enum Size {
+    Small,
+}
+
+fn main() {}